@@ -8,8 +8,9 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_template_runtime::{opaque::Block, AccountId, Balance, GameKey, Index};
 use sc_transaction_pool_api::TransactionPool;
+use schelling_game_shared::types::{CommitChoice, JurorStats};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
@@ -37,16 +38,31 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: sortition_sum_game_rpc::SortitionSumGameRuntimeApi<Block, GameKey, AccountId>,
+	C::Api: schelling_game_shared_rpc::SchellingGameSharedRuntimeApi<
+		Block,
+		GameKey,
+		AccountId,
+		CommitChoice,
+		JurorStats,
+	>,
 	P: TransactionPool + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use schelling_game_shared_rpc::{SchellingGameShared, SchellingGameSharedApiServer};
+	use sortition_sum_game_rpc::{SortitionSumGame, SortitionSumGameApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcModule::new(());
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(SortitionSumGame::<_, (Block, GameKey)>::new(client.clone()).into_rpc())?;
+	module.merge(
+		SchellingGameShared::<_, (Block, GameKey, CommitChoice, JurorStats)>::new(client)
+			.into_rpc(),
+	)?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
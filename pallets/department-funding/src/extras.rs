@@ -2,8 +2,38 @@ use crate::*;
 
 impl<T: Config> Pallet<T> {
 
-	pub(super) fn get_phase_data() -> PhaseData<T> {
-		T::SchellingGameSharedSource::create_phase_data(50, 5, 3, 100, (100, 100))
+	/// Phase data set for the game type in schelling game shared, or the default of the pallet
+	pub(super) fn get_phase_data() -> Result<PhaseData<T>, DispatchError> {
+		let game_type = SchellingGameType::DepartmentScore;
+		if let Some(phase_data) = T::SchellingGameSharedSource::get_phase_data_link(game_type) {
+			return Ok(phase_data);
+		}
+		let phase_lengths = PhaseLengths {
+			evidence: 50,
+			staking: 50,
+			// Longer than the availability delay of the randomness of the runtime
+			drawing: 100,
+			commit: 50,
+			vote: 50,
+			appeal: 50,
+		};
+		T::SchellingGameSharedSource::create_phase_data(phase_lengths, 5, 3, 100, (100, 100), 2)
+	}
+
+	/// Key of the schelling game of `department_id` started at `block_number`
+	pub(super) fn game_key(
+		department_id: DeparmentId,
+		block_number: BlockNumberOf<T>,
+	) -> SumTreeNameType<T> {
+		DepartmentScoreKey { department_id, block_number }.into()
+	}
+
+	/// Phase data of the game, stored in schelling game shared when the game started
+	pub(super) fn get_game_phase_data(
+		key: SumTreeNameType<T>,
+	) -> Result<PhaseData<T>, DispatchError> {
+		T::SchellingGameSharedSource::get_game_phase_data_link(key)
+			.ok_or(Error::<T>::GamePhaseDataNotFound.into())
 	}
 	pub fn ensure_min_stake_deparment(department_id: DeparmentId) -> DispatchResult {
 		let stake = DepartmentStakeBalance::<T>::get(department_id);
@@ -23,3 +53,20 @@ impl<T: Config> Pallet<T> {
 		input.saturated_into::<BlockNumberOf<T>>()
 	}
 }
+
+impl<T: Config> OnGameConcluded<DepartmentScoreKeyOf<T>, GameResult<BlockNumberOf<T>>>
+	for Pallet<T>
+{
+	/// Score of the department is recorded in `DepartmentScore`
+	fn on_game_concluded(
+		key: DepartmentScoreKeyOf<T>,
+		result: GameResult<BlockNumberOf<T>>,
+	) -> DispatchResult {
+		if let (GameOutcome::Score, Some(score)) = (result.outcome, result.aggregated_score) {
+			let department_id = key.department_id;
+			<DepartmentScore<T>>::insert(department_id, score);
+			Self::deposit_event(Event::DepartmentScoreRecorded { department_id, score });
+		}
+		Ok(())
+	}
+}
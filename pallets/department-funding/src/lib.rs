@@ -18,6 +18,9 @@ pub use weights::*;
 
 mod extras;
 
+mod types;
+pub use types::DepartmentScoreKey;
+
 use frame_support::sp_runtime::traits::Saturating;
 use frame_support::sp_runtime::SaturatedConversion;
 use frame_support::sp_std::prelude::*;
@@ -33,14 +36,19 @@ use pallet_support::{
 	ensure_content_is_valid, new_who_and_when, remove_from_vec, Content, PositiveExternalityPostId,
 	WhoAndWhen, WhoAndWhenOf,
 };
-use schelling_game_shared::types::{Period, RangePoint, SchellingGameType, PhaseData};
-use schelling_game_shared_link::SchellingGameSharedLink;
+use schelling_game_shared::types::{
+	GameOutcome, GameResult, Period, PhaseData, PhaseLengths, RangePoint, SchellingGameType,
+	ScoreAggregation,
+};
+use schelling_game_shared::WeightInfo as SchellingGameWeightInfo;
+use schelling_game_shared_link::{OnGameConcluded, SchellingGameSharedLink};
 use shared_storage_link::SharedStorageLink;
-use sortition_sum_game::types::SumTreeName;
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
-pub type SumTreeNameType<T> = SumTreeName<AccountIdOf<T>, BlockNumberOf<T>>;
+pub type SumTreeNameType<T> = <T as Config>::SchellingGameKey;
+pub type DepartmentScoreKeyOf<T> = DepartmentScoreKey<BlockNumberOf<T>>;
+type SchellingGameWeight<T> = <T as schelling_game_shared::Config>::WeightInfo;
 type DeparmentId = u128;
 
 #[frame_support::pallet]
@@ -50,7 +58,6 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -62,15 +69,22 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		type SharedStorageSource: SharedStorageLink<AccountId = AccountIdOf<Self>>;
+		/// Key of the games of `SchellingGameSharedSource`, defined by the runtime
+		/// Games of this pallet are keyed by `DepartmentScoreKey`
+		type SchellingGameKey: Parameter + From<DepartmentScoreKey<Self::BlockNumber>>;
+
 		type SchellingGameSharedSource: SchellingGameSharedLink<
-			SumTreeName = SumTreeName<Self::AccountId, Self::BlockNumber>,
+			SumTreeName = Self::SchellingGameKey,
 			SchellingGameType = SchellingGameType,
 			BlockNumber = Self::BlockNumber,
 			AccountId = AccountIdOf<Self>,
 			Balance = BalanceOf<Self>,
 			RangePoint = RangePoint,
+			ScoreAggregation = ScoreAggregation,
 			Period = Period,
 			PhaseData = PhaseData<Self>,
+			PhaseLengths = PhaseLengths,
+			GameResult = GameResult<Self::BlockNumber>,
 
 		>;
 		type Currency: ReservableCurrency<Self::AccountId>;
@@ -99,6 +113,12 @@ pub mod pallet {
 	pub type ValidationDepartmentBlock<T: Config> =
 		StorageMap<_, Blake2_128Concat, DeparmentId, BlockNumberOf<T>, ValueQuery>;
 
+	/// Score of the department from the latest schelling game, multiplied by 1000
+	#[pallet::storage]
+	#[pallet::getter(fn department_score)]
+	pub type DepartmentScore<T: Config> =
+		StorageMap<_, Blake2_128Concat, DeparmentId, i64, ValueQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -107,6 +127,8 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		SomethingStored { something: u32, who: T::AccountId },
+		/// Department score recorded when its schelling game concluded. [department_id, score]
+		DepartmentScoreRecorded { department_id: DeparmentId, score: i64 },
 	}
 
 	// Errors inform users that something went wrong.
@@ -119,6 +141,7 @@ pub mod pallet {
 		LessThanMinStake,
 		CannotStakeNow,
 		ChoiceOutOfRange,
+		GamePhaseDataNotFound,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -185,17 +208,19 @@ pub mod pallet {
 			// println!("{:?}", storage_main_block);
 			// println!("{:?}", pe_block_number);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: storage_main_block.clone(),
-			};
+			let key = Self::game_key(department_id, storage_main_block.clone());
 
 			// let game_type = SchellingGameType::PositiveExternality;
 
 			if storage_main_block > pe_block_number {
 				<ValidationDepartmentBlock<T>>::insert(department_id, storage_main_block);
 				// check what if called again
-				T::SchellingGameSharedSource::set_to_staking_period_pe_link(key.clone(), now)?;
+				let phase_data = Self::get_phase_data()?;
+				T::SchellingGameSharedSource::set_to_staking_period_pe_link(
+					key.clone(),
+					phase_data,
+					now,
+				)?;
 				T::SchellingGameSharedSource::create_tree_helper_link(key, 3)?;
 
 			//  println!("{:?}", data);
@@ -220,18 +245,17 @@ pub mod pallet {
 
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::apply_jurors_helper_link(key, phase_data, who, stake)?;
 
 			Ok(())
 		}
 
+		/// Change the period manually, periods are also changed automatically in `on_initialize`
+		/// of schelling game shared when the period is over
 		#[pallet::call_index(3)]
 		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
 		pub fn pass_period(origin: OriginFor<T>, department_id: DeparmentId) -> DispatchResult {
@@ -239,13 +263,10 @@ pub mod pallet {
 
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 			T::SchellingGameSharedSource::change_period_link(key, phase_data, now)?;
 
 			Ok(())
@@ -262,12 +283,9 @@ pub mod pallet {
 
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::draw_jurors_helper_link(key, phase_data, iterations)?;
 
@@ -282,10 +300,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::unstaking_helper_link(key, who)?;
 			Ok(())
@@ -301,10 +316,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::commit_vote_for_score_helper_link(key, who, vote_commit)?;
 			Ok(())
@@ -324,39 +336,36 @@ pub mod pallet {
 
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
 
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(department_id, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::reveal_vote_score_helper_link(key, who, choice, salt)?;
 			Ok(())
 		}
 
+		/// Settle at most `limit` jurors of the game
 		#[pallet::call_index(8)]
-		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
-		pub fn get_incentives(origin: OriginFor<T>, department_id: DeparmentId) -> DispatchResult {
+		#[pallet::weight(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(*limit))]
+		pub fn get_incentives(
+			origin: OriginFor<T>,
+			department_id: DeparmentId,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
 			let _who = ensure_signed(origin)?;
 			let pe_block_number = <ValidationDepartmentBlock<T>>::get(department_id);
-			let key = SumTreeName::DepartmentScore {
-				department_id,
-				block_number: pe_block_number.clone(),
-			};
-
-			let phase_data = Self::get_phase_data();
-			T::SchellingGameSharedSource::get_incentives_score_schelling_helper_link(
-				key.clone(),
-				phase_data,
-				RangePoint::ZeroToFive,
-			)?;
-
-			let score = T::SchellingGameSharedSource::get_mean_value_link(key.clone());
-			// // println!("Score {:?}", score);
-
-			// To do
-			// T::SharedStorageSource::set_positive_externality_link(user_to_calculate, score)?;
-
-			Ok(())
+			let key = Self::game_key(department_id, pe_block_number.clone());
+
+			let phase_data = Self::get_game_phase_data(key.clone())?;
+			let (processed, _settled) =
+				T::SchellingGameSharedSource::settle_score_schelling_helper_link(
+					key.clone(),
+					phase_data,
+					RangePoint::ZeroToFive,
+					ScoreAggregation::Median,
+					limit,
+				)?;
+
+			// Score is recorded by `OnGameConcluded` when settlement starts
+			Ok(Some(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(processed)).into())
 		}
 	}
 }
@@ -1,5 +1,6 @@
 use crate as pallet_template;
-use frame_support::{traits::{ConstU16, ConstU64, GenesisBuild}};
+use frame_support::{traits::{ConstU16, ConstU32, ConstU64, GenesisBuild}};
+use pallet_template::DepartmentScoreKey;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -9,6 +10,7 @@ use sp_runtime::{
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 use frame_support_test::TestRandomness;
+use schelling_game_shared::randomness::InstantRandomness;
 
 
 // Configure a mock runtime to test the pallet.
@@ -58,12 +60,14 @@ impl frame_system::Config for Test {
 impl shared_storage::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type MaxApprovedCitizens = ConstU32<100>;
 }
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type SharedStorageSource = SharedStorage;
 	type Currency = Balances; // New code
+	type SchellingGameKey = DepartmentScoreKey<u64>;
 	type SchellingGameSharedSource = SchellingGameShared;
 }
 
@@ -86,16 +90,25 @@ impl pallet_balances::Config for Test {
 impl schelling_game_shared::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = DepartmentScoreKey<u64>;
 	type Currency = Balances; // New code
-	type RandomnessSource = TestRandomness<Self>;
+	type RandomnessSource = InstantRandomness<TestRandomness<Self>>;
 	type Slash = ();
 	type Reward = ();
+	type MaxPeriodChangesPerBlock = ConstU32<50>;
+	type GameRetentionPeriod = ConstU64<100>;
+	type MaxGamesPrunedPerBlock = ConstU32<50>;
+	type MaxDrawsPerRound = ConstU32<50>;
+	type MinSaltLength = ConstU32<4>;
+	type PhaseDataOrigin = frame_system::EnsureRoot<u64>;
+	type OnGameConcluded = TemplateModule;
 	type SortitionSumGameSource = SortitionSumGame;
 }
 
 impl sortition_sum_game::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = DepartmentScoreKey<u64>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -0,0 +1,13 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Key of the schelling game of the score of a department
+/// Converted by the runtime into its `SchellingGameKey`
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct DepartmentScoreKey<BlockNumber> {
+	pub department_id: u128,
+	pub block_number: BlockNumber,
+}
@@ -94,7 +94,13 @@ impl<T: Config> Pallet<T> {
 					// defensive-only: Members and runners-up are disjoint. This will always be err and
 					// give us an index to insert.
 					if let Err(index) = members.binary_search_by(|m| m.who.cmp(&next_best.who)) {
-						members.insert(index, next_best.clone());
+						// a member was removed above, so there is always room for the runner-up.
+						if members.try_insert(index, next_best.clone()).is_err() {
+							log::error!(
+								target: "runtime::elections-phragmen",
+								"No room for the runner-up replacing a member.",
+							);
+						}
 					} else {
 						// overlap. This can never happen. If so, it seems like our intended replacement
 						// is already a member, so not much more to do.
@@ -17,12 +17,16 @@ pub mod weights;
 pub use weights::*;
 
 mod extras;
+pub mod migrations;
 mod types;
 
 /// The maximum votes allowed per voter.
 pub const MAXIMUM_VOTE: usize = 16;
 
-use crate::types::{DepartmentDetails, Renouncing, SeatHolder, Voter};
+/// The maximum length of the name and of the details of a department.
+pub const MAXIMUM_DEPARTMENT_DETAILS_LENGTH: u32 = 256;
+
+use crate::types::{DepartmentDetails, MaxVotes, Renouncing, SeatHolder, Voter};
 
 use frame_support::traits::{
 	defensive_prelude::*, Currency, CurrencyToVote, Get, OnUnbalanced, ReservableCurrency,
@@ -47,8 +51,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 bounds candidates, seat holders, votes and department details
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -78,6 +85,18 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type CandidacyBond: Get<BalanceOf<Self>>;
+
+		/// Maximum number of candidates of a department in a term
+		#[pallet::constant]
+		type MaxCandidates: Get<u32>;
+
+		/// Maximum number of members of a department, desired members above it are not elected
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Maximum number of runners-up of a department, desired runners-up above it are not kept
+		#[pallet::constant]
+		type MaxRunnersUp: Get<u32>;
 	}
 
 	// The pallet's runtime storage items.
@@ -90,8 +109,13 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn candidates)]
-	pub type Candidates<T: Config> =
-		StorageMap<_, Blake2_128Concat, u128, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>; // departmentid => Vec(Candidate Account Id and deposit)
+	pub type Candidates<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxCandidates>,
+		ValueQuery,
+	>; // departmentid => Vec(Candidate Account Id and deposit)
 
 	// Departments will remain in separate pallet
 	#[pallet::storage]
@@ -135,7 +159,7 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		u128,
-		Vec<SeatHolder<T::AccountId, BalanceOf<T>>>,
+		BoundedVec<SeatHolder<T::AccountId, BalanceOf<T>>, T::MaxMembers>,
 		ValueQuery,
 	>; // department id => Vec <SeatHolder>
 
@@ -149,7 +173,7 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		u128,
-		Vec<SeatHolder<T::AccountId, BalanceOf<T>>>,
+		BoundedVec<SeatHolder<T::AccountId, BalanceOf<T>>, T::MaxRunnersUp>,
 		ValueQuery,
 	>; // department id => Vec<SeatHolder>
 
@@ -213,6 +237,8 @@ pub mod pallet {
 		InsufficientCandidateFunds,
 		NotMember,
 		InvalidRenouncing,
+		/// Candidates of the department reached `MaxCandidates`
+		TooManyCandidates,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -233,7 +259,8 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			// votes should not be empty and more than `MAXIMUM_VOTE` in any case.
-			ensure!(votes.len() <= MAXIMUM_VOTE, Error::<T>::MaximumVotesExceeded);
+			let votes: BoundedVec<T::AccountId, MaxVotes> =
+				votes.try_into().map_err(|_| Error::<T>::MaximumVotesExceeded)?;
 			ensure!(!votes.is_empty(), Error::<T>::NoVotes);
 
 			let candidates_count = <Candidates<T>>::decode_len(&departmentid).unwrap_or(0);
@@ -288,13 +315,15 @@ pub mod pallet {
 
 			ensure!(!Self::is_member(&who, departmentid), Error::<T>::MemberSubmit);
 			ensure!(!Self::is_runner_up(&who, departmentid), Error::<T>::RunnerUpSubmit);
+			ensure!(actual_count < T::MaxCandidates::get() as usize, Error::<T>::TooManyCandidates);
 
 			T::Currency::reserve(&who, T::CandidacyBond::get())
 				.map_err(|_| Error::<T>::InsufficientCandidateFunds)?;
 
-			<Candidates<T>>::mutate(departmentid, |c| {
-				c.insert(index, (who, T::CandidacyBond::get()))
-			});
+			<Candidates<T>>::try_mutate(departmentid, |c| {
+				c.try_insert(index, (who, T::CandidacyBond::get()))
+					.map_err(|_| Error::<T>::TooManyCandidates)
+			})?;
 			Ok(None.into())
 		}
 
@@ -347,10 +376,13 @@ pub mod pallet {
 		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
 		pub fn do_phragmen(origin: OriginFor<T>, departmentid: u128) -> DispatchResult {
 			let _who = ensure_signed(origin)?;
-			let desired_seats = <DesiredMembers<T>>::get(&departmentid) as usize;
-			let desired_runners_up = <DesiredRunnersup<T>>::get(&departmentid) as usize;
+			// seats are bounded by `MaxMembers` and `MaxRunnersUp`.
+			let desired_seats = (<DesiredMembers<T>>::get(&departmentid) as usize)
+				.min(T::MaxMembers::get() as usize);
+			let desired_runners_up = (<DesiredRunnersup<T>>::get(&departmentid) as usize)
+				.min(T::MaxRunnersUp::get() as usize);
 			let num_to_elect = desired_runners_up + desired_seats;
-			let mut candidates_and_deposit = Self::candidates(&departmentid);
+			let mut candidates_and_deposit = Self::candidates(&departmentid).into_inner();
 
 			// add all the previous members and runners-up as candidates as well.
 			candidates_and_deposit
@@ -369,7 +401,7 @@ pub mod pallet {
 			let _to_votes = |b: BalanceOf<T>| T::CurrencyToVote::to_vote(b, total_issuance);
 			let to_balance = |e: ExtendedBalance| T::CurrencyToVote::to_currency(e, total_issuance);
 			let voters_and_score = <Voting<T>>::iter_prefix(&departmentid)
-				.map(|(voter, Voter { score, votes, .. })| (voter, score, votes))
+				.map(|(voter, Voter { score, votes, .. })| (voter, score, votes.into_inner()))
 				.collect::<Vec<_>>();
 
 			let _ = sp_npos_elections::seq_phragmen(
@@ -446,28 +478,34 @@ pub mod pallet {
 				// fetch deposits from the one recorded one. This will make sure that a
 				// candidate who submitted candidacy before a change to candidacy deposit will
 				// have the correct amount recorded.
+				// nothing is truncated, there are at most `desired_seats` members and
+				// `desired_runners_up` runners-up.
 				<Members<T>>::insert(
 					departmentid,
-					new_members_sorted_by_id
-						.iter()
-						.map(|(who, stake)| SeatHolder {
-							deposit: deposit_of_candidate(who),
-							who: who.clone(),
-							stake: *stake,
-						})
-						.collect::<Vec<_>>(),
+					BoundedVec::<_, T::MaxMembers>::truncate_from(
+						new_members_sorted_by_id
+							.iter()
+							.map(|(who, stake)| SeatHolder {
+								deposit: deposit_of_candidate(who),
+								who: who.clone(),
+								stake: *stake,
+							})
+							.collect::<Vec<_>>(),
+					),
 				);
 
 				<RunnersUp<T>>::insert(
 					departmentid,
-					new_runners_up_sorted_by_rank
-						.into_iter()
-						.map(|(who, stake)| SeatHolder {
-							deposit: deposit_of_candidate(&who),
-							who,
-							stake,
-						})
-						.collect::<Vec<_>>(),
+					BoundedVec::<_, T::MaxRunnersUp>::truncate_from(
+						new_runners_up_sorted_by_rank
+							.into_iter()
+							.map(|(who, stake)| SeatHolder {
+								deposit: deposit_of_candidate(&who),
+								who,
+								stake,
+							})
+							.collect::<Vec<_>>(),
+					),
 				);
 
 				// clean candidates.
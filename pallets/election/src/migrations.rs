@@ -0,0 +1,149 @@
+//! Storage migrations of the election pallet
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+pub mod v1 {
+	use super::*;
+
+	/// Department details before version 1, name and details are not bounded
+	#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct OldDepartmentDetails {
+		pub name: Vec<u8>,
+		pub locationid: u128,
+		pub details: Vec<u8>,
+		pub departmentid: u128,
+	}
+
+	/// Voter before version 1, votes are not bounded
+	#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct OldVoter<AccountId> {
+		pub votes: Vec<AccountId>,
+		pub score: u64,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type Candidates<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, u128, Vec<(AccountIdOf<T>, BalanceOf<T>)>>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type Department<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, u128, OldDepartmentDetails>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type Members<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		u128,
+		Vec<SeatHolder<AccountIdOf<T>, BalanceOf<T>>>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type RunnersUp<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		u128,
+		Vec<SeatHolder<AccountIdOf<T>, BalanceOf<T>>>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type Voting<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		u128,
+		Twox64Concat,
+		AccountIdOf<T>,
+		OldVoter<AccountIdOf<T>>,
+	>;
+
+	/// Bound candidates, members, runners-up, votes and department details
+	/// Candidates after the first `MaxCandidates` by account, members after the first
+	/// `MaxMembers` by account and runners-up below the best `MaxRunnersUp` lose their seat and
+	/// get their deposit back
+	/// Votes and department details are truncated
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+
+			let candidates: Vec<_> = Candidates::<T>::drain().collect();
+			for (departmentid, mut candidates) in candidates {
+				let kept = candidates.len().min(T::MaxCandidates::get() as usize);
+				for (who, deposit) in candidates.split_off(kept) {
+					T::Currency::unreserve(&who, deposit);
+					writes += 1;
+				}
+				let candidates = BoundedVec::<_, T::MaxCandidates>::truncate_from(candidates);
+				<crate::Candidates<T>>::insert(departmentid, candidates);
+				reads += 1;
+				writes += 1;
+			}
+
+			let members: Vec<_> = Members::<T>::drain().collect();
+			for (departmentid, mut members) in members {
+				let kept = members.len().min(T::MaxMembers::get() as usize);
+				for member in members.split_off(kept) {
+					T::Currency::unreserve(&member.who, member.deposit);
+					writes += 1;
+				}
+				let members = BoundedVec::<_, T::MaxMembers>::truncate_from(members);
+				<crate::Members<T>>::insert(departmentid, members);
+				reads += 1;
+				writes += 1;
+			}
+
+			// Runners-up are sorted from worst to best
+			let runners_up: Vec<_> = RunnersUp::<T>::drain().collect();
+			for (departmentid, mut runners_up) in runners_up {
+				let dropped = runners_up.len().saturating_sub(T::MaxRunnersUp::get() as usize);
+				for runner_up in runners_up.drain(..dropped) {
+					T::Currency::unreserve(&runner_up.who, runner_up.deposit);
+					writes += 1;
+				}
+				let runners_up = BoundedVec::<_, T::MaxRunnersUp>::truncate_from(runners_up);
+				<crate::RunnersUp<T>>::insert(departmentid, runners_up);
+				reads += 1;
+				writes += 1;
+			}
+
+			let voting: Vec<_> = Voting::<T>::drain().collect();
+			for (departmentid, who, voter) in voting {
+				let votes = BoundedVec::truncate_from(voter.votes);
+				<crate::Voting<T>>::insert(departmentid, who, Voter { votes, score: voter.score });
+				reads += 1;
+				writes += 1;
+			}
+
+			let departments: Vec<_> = Department::<T>::drain().collect();
+			for (departmentid, department) in departments {
+				let department = DepartmentDetails {
+					name: BoundedVec::truncate_from(department.name),
+					locationid: department.locationid,
+					details: BoundedVec::truncate_from(department.details),
+					departmentid: department.departmentid,
+				};
+				<crate::Department<T>>::insert(departmentid, department);
+				reads += 1;
+				writes += 1;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
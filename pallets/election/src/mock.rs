@@ -1,5 +1,5 @@
 use crate as pallet_template;
-use frame_support::{parameter_types,dispatch::DispatchResultWithPostInfo, traits::{ConstU16, ConstU64}};
+use frame_support::{parameter_types,dispatch::DispatchResultWithPostInfo, traits::{ConstU16, ConstU32, ConstU64}};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -64,6 +64,9 @@ impl pallet_template::Config for Test {
 	type KickedMember = ();
 	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
 	type CandidacyBond = CandidacyBond;
+	type MaxCandidates = ConstU32<20>;
+	type MaxMembers = ConstU32<5>;
+	type MaxRunnersUp = ConstU32<5>;
 }
 
 impl pallet_balances::Config for Test {
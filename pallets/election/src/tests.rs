@@ -1,5 +1,8 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{migrations::v1, mock::*, types::SeatHolder, Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+};
 
 
 #[test]
@@ -48,6 +51,22 @@ fn candidates_are_always_sorted() {
 	});
 }
 
+#[test]
+fn candidates_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let departmentid = 1;
+		for who in 1..=20 {
+			assert_ok!(submit_candidacy(RuntimeOrigin::signed(who), departmentid));
+		}
+		assert_noop!(
+			submit_candidacy(RuntimeOrigin::signed(21), departmentid),
+			Error::<Test>::TooManyCandidates
+		);
+		assert_eq!(candidate_ids(departmentid).len(), 20);
+		assert_ok!(submit_candidacy(RuntimeOrigin::signed(21), departmentid + 1));
+	});
+}
+
 #[test]
 fn simple_voting_should_work() {
 	new_test_ext().execute_with(|| {
@@ -81,3 +100,63 @@ fn runners_up_should_be_kept() {
 		assert_eq!(runners_up_ids(departmentid), vec![3, 2]);
 	});
 }
+
+#[test]
+fn votes_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let departmentid = 1;
+		for who in 1..=17 {
+			assert_ok!(submit_candidacy(RuntimeOrigin::signed(who), departmentid));
+		}
+		assert_noop!(
+			vote(RuntimeOrigin::signed(20), departmentid, (1..=17).collect(), 20),
+			Error::<Test>::MaximumVotesExceeded
+		);
+		assert_ok!(vote(RuntimeOrigin::signed(20), departmentid, (1..=16).collect(), 20));
+		assert_eq!(Elections::voting(departmentid, 20).votes.len(), 16);
+	});
+}
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Elections>();
+		let departmentid = 1;
+		let candidates: Vec<(u64, u64)> = (1..=21).map(|who| (who, 3)).collect();
+		let members: Vec<SeatHolder<u64, u64>> =
+			(22..=27).map(|who| SeatHolder { who, stake: 10, deposit: 3 }).collect();
+		let runners_up: Vec<SeatHolder<u64, u64>> =
+			(28..=33).map(|who| SeatHolder { who, stake: 10, deposit: 3 }).collect();
+		for who in 1..=33 {
+			assert_ok!(Balances::reserve(&who, 3));
+		}
+		v1::Candidates::<Test>::insert(departmentid, candidates);
+		v1::Members::<Test>::insert(departmentid, members);
+		v1::RunnersUp::<Test>::insert(departmentid, runners_up);
+		let voter = v1::OldVoter { votes: vec![22, 23], score: 20 };
+		v1::Voting::<Test>::insert(departmentid, 1, voter);
+		let department = v1::OldDepartmentDetails {
+			name: vec![1; 300],
+			locationid: 1,
+			details: vec![2; 10],
+			departmentid,
+		};
+		v1::Department::<Test>::insert(departmentid, department);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Elections::on_chain_storage_version(), 1);
+		assert_eq!(candidate_ids(departmentid), (1..=20).collect::<Vec<_>>());
+		assert_eq!(balances(&20), (299997, 3));
+		assert_eq!(balances(&21), (300000, 0));
+		assert_eq!(members_ids(departmentid), vec![22, 23, 24, 25, 26]);
+		assert_eq!(balances(&27), (300000, 0));
+		// The worst runner-up is dropped
+		assert_eq!(runners_up_ids(departmentid), vec![29, 30, 31, 32, 33]);
+		assert_eq!(balances(&28), (300000, 0));
+		assert_eq!(balances(&29), (299997, 3));
+		assert_eq!(Elections::voting(departmentid, 1).votes.into_inner(), vec![22, 23]);
+		let department = Elections::department(departmentid).unwrap();
+		assert_eq!(department.name.len(), 256);
+		assert_eq!(department.details.into_inner(), vec![2; 10]);
+	});
+}
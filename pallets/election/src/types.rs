@@ -1,18 +1,36 @@
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
 
+use crate::{MAXIMUM_DEPARTMENT_DETAILS_LENGTH, MAXIMUM_VOTE};
+
+/// Bound of the votes of a voter
+pub type MaxVotes = ConstU32<{ MAXIMUM_VOTE as u32 }>;
+
+/// Name or details of a department
+pub type DepartmentDetailsBytes = BoundedVec<u8, ConstU32<MAXIMUM_DEPARTMENT_DETAILS_LENGTH>>;
+
 #[derive(
-	PartialEq, Eq, PartialOrd, Ord, Default, Clone, Encode, Decode, RuntimeDebug, TypeInfo,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Default,
+	Clone,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
 )]
 pub struct DepartmentDetails {
-	pub name: Vec<u8>,
+	pub name: DepartmentDetailsBytes,
 	pub locationid: u128,
-	pub details: Vec<u8>,
+	pub details: DepartmentDetailsBytes,
 	pub departmentid: u128,
 }
 
 /// A holder of a seat as either a member or a runner-up.
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo, MaxEncodedLen)]
 pub struct SeatHolder<AccountId, Balance> {
 	/// The holder.
 	pub who: AccountId,
@@ -25,18 +43,18 @@ pub struct SeatHolder<AccountId, Balance> {
 }
 
 /// An active voter.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Voter<AccountId> {
 	/// The members being backed.
-	pub votes: Vec<AccountId>,
+	pub votes: BoundedVec<AccountId, MaxVotes>,
 	/// The amount of stake placed on this vote.
 	pub score: u64,
 }
 
 impl<AccountId> Default for Voter<AccountId> {
 	fn default() -> Self {
-		Self { votes: vec![], score: Default::default() }
+		Self { votes: BoundedVec::default(), score: Default::default() }
 	}
 }
 
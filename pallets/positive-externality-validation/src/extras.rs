@@ -28,8 +28,38 @@ impl<T: Config> PositiveExternalityPost<T> {
 
 impl<T: Config> Pallet<T> {
 
-	pub(super) fn get_phase_data() -> PhaseData<T> {
-		T::SchellingGameSharedSource::create_phase_data(50, 5, 3, 100, (100, 100))
+	/// Phase data set for the game type in schelling game shared, or the default of the pallet
+	pub(super) fn get_phase_data() -> Result<PhaseData<T>, DispatchError> {
+		let game_type = SchellingGameType::PositiveExternality;
+		if let Some(phase_data) = T::SchellingGameSharedSource::get_phase_data_link(game_type) {
+			return Ok(phase_data);
+		}
+		let phase_lengths = PhaseLengths {
+			evidence: 50,
+			staking: 50,
+			// Longer than the availability delay of the randomness of the runtime
+			drawing: 100,
+			commit: 50,
+			vote: 50,
+			appeal: 50,
+		};
+		T::SchellingGameSharedSource::create_phase_data(phase_lengths, 5, 3, 100, (100, 100), 2)
+	}
+
+	/// Key of the schelling game of `user_address` started at `block_number`
+	pub(super) fn game_key(
+		user_address: T::AccountId,
+		block_number: BlockNumberOf<T>,
+	) -> SumTreeNameType<T> {
+		PositiveExternalityKey { user_address, block_number }.into()
+	}
+
+	/// Phase data of the game, stored in schelling game shared when the game started
+	pub(super) fn get_game_phase_data(
+		key: SumTreeNameType<T>,
+	) -> Result<PhaseData<T>, DispatchError> {
+		T::SchellingGameSharedSource::get_game_phase_data_link(key)
+			.ok_or(Error::<T>::GamePhaseDataNotFound.into())
 	}
 
 	pub fn ensure_validation_on_positive_externality(account: T::AccountId) -> DispatchResult {
@@ -61,11 +91,23 @@ impl<T: Config> Pallet<T> {
 		let pe_block_number =
 			<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-		let key = SumTreeName::PositiveExternality {
-			user_address: user_to_calculate,
-			block_number: pe_block_number.clone(),
-		};
+		let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
 		T::SchellingGameSharedSource::get_drawn_jurors(key)
 	}
 }
+
+impl<T: Config> OnGameConcluded<PositiveExternalityKeyOf<T>, GameResult<BlockNumberOf<T>>>
+	for Pallet<T>
+{
+	/// Positive externality score of the user is set in shared storage
+	fn on_game_concluded(
+		key: PositiveExternalityKeyOf<T>,
+		result: GameResult<BlockNumberOf<T>>,
+	) -> DispatchResult {
+		if let (GameOutcome::Score, Some(score)) = (result.outcome, result.aggregated_score) {
+			T::SharedStorageSource::set_positive_externality_link(key.user_address, score)?;
+		}
+		Ok(())
+	}
+}
@@ -17,8 +17,9 @@ pub mod weights;
 pub use weights::*;
 
 mod extras;
+pub mod migrations;
 pub mod types;
-pub use types::{PositiveExternalityPost, FIRST_POST_ID};
+pub use types::{PositiveExternalityKey, PositiveExternalityPost, FIRST_POST_ID};
 
 use frame_support::sp_runtime::traits::Saturating;
 use frame_support::sp_runtime::SaturatedConversion;
@@ -35,14 +36,19 @@ use pallet_support::{
 	ensure_content_is_valid, new_who_and_when, remove_from_vec, Content, PositiveExternalityPostId,
 	WhoAndWhen, WhoAndWhenOf,
 };
-use schelling_game_shared::types::{Period, RangePoint, SchellingGameType, PhaseData};
-use schelling_game_shared_link::SchellingGameSharedLink;
+use schelling_game_shared::types::{
+	GameOutcome, GameResult, Period, PhaseData, PhaseLengths, RangePoint, SchellingGameType,
+	ScoreAggregation,
+};
+use schelling_game_shared::WeightInfo as SchellingGameWeightInfo;
+use schelling_game_shared_link::{OnGameConcluded, SchellingGameSharedLink};
 use shared_storage_link::SharedStorageLink;
-use sortition_sum_game::types::SumTreeName;
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
-pub type SumTreeNameType<T> = SumTreeName<AccountIdOf<T>, BlockNumberOf<T>>;
+pub type SumTreeNameType<T> = <T as Config>::SchellingGameKey;
+pub type PositiveExternalityKeyOf<T> = PositiveExternalityKey<AccountIdOf<T>, BlockNumberOf<T>>;
+type SchellingGameWeight<T> = <T as schelling_game_shared::Config>::WeightInfo;
 
 
 #[frame_support::pallet]
@@ -51,8 +57,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 bounds the content of posts and the posts of a user
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -64,17 +73,29 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		type SharedStorageSource: SharedStorageLink<AccountId = AccountIdOf<Self>>;
+		/// Key of the games of `SchellingGameSharedSource`, defined by the runtime
+		/// Games of this pallet are keyed by `PositiveExternalityKey`
+		type SchellingGameKey: Parameter
+			+ From<PositiveExternalityKey<Self::AccountId, Self::BlockNumber>>;
+
 		type SchellingGameSharedSource: SchellingGameSharedLink<
-			SumTreeName = SumTreeName<Self::AccountId, Self::BlockNumber>,
+			SumTreeName = Self::SchellingGameKey,
 			SchellingGameType = SchellingGameType,
 			BlockNumber = Self::BlockNumber,
 			AccountId = AccountIdOf<Self>,
 			Balance = BalanceOf<Self>,
 			RangePoint = RangePoint,
+			ScoreAggregation = ScoreAggregation,
 			Period = Period,
 			PhaseData = PhaseData<Self>,
+			PhaseLengths = PhaseLengths,
+			GameResult = GameResult<Self::BlockNumber>,
 		>;
 		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Maximum number of positive externality posts of a user
+		#[pallet::constant]
+		type MaxPostsPerUser: Get<u32>;
 	}
 
 	// The pallet's runtime storage items.
@@ -108,8 +129,13 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn positive_externality_evidence)]
-	pub type PositiveExternalityEvidence<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, Vec<PositiveExternalityPostId>, ValueQuery>;
+	pub type PositiveExternalityEvidence<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<PositiveExternalityPostId, T::MaxPostsPerUser>,
+		ValueQuery,
+	>;
 
 	#[pallet::type_value]
 	pub fn MinimumPositiveExternalityStake<T: Config>() -> BalanceOf<T> {
@@ -154,6 +180,10 @@ pub mod pallet {
 		LessThanMinStake,
 		CannotStakeNow,
 		ChoiceOutOfRange,
+		NotAGameParty,
+		GamePhaseDataNotFound,
+		/// The user reached `MaxPostsPerUser`
+		TooManyPosts,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -178,7 +208,8 @@ pub mod pallet {
 			let new_post: PositiveExternalityPost<T> =
 				PositiveExternalityPost::new(new_post_id, creator.clone(), content.clone());
 
-			PositiveExternalityEvidence::<T>::mutate(creator, |ids| ids.push(new_post_id));
+			PositiveExternalityEvidence::<T>::try_mutate(creator, |ids| ids.try_push(new_post_id))
+				.map_err(|_| Error::<T>::TooManyPosts)?;
 
 			PositiveExternalityPostById::insert(new_post_id, new_post);
 			NextPositiveExternalityPostId::<T>::mutate(|n| {
@@ -249,10 +280,7 @@ pub mod pallet {
 			// println!("{:?}", storage_main_block);
 			// println!("{:?}", pe_block_number);
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate.clone(),
-				block_number: storage_main_block.clone(),
-			};
+			let key = Self::game_key(user_to_calculate.clone(), storage_main_block.clone());
 
 			// let game_type = SchellingGameType::PositiveExternality;
 
@@ -262,7 +290,12 @@ pub mod pallet {
 					storage_main_block,
 				);
 				// check what if called again
-				T::SchellingGameSharedSource::set_to_staking_period_pe_link(key.clone(), now)?;
+				let phase_data = Self::get_phase_data()?;
+				T::SchellingGameSharedSource::set_to_staking_period_pe_link(
+					key.clone(),
+					phase_data,
+					now,
+				)?;
 				T::SchellingGameSharedSource::create_tree_helper_link(key, 3)?;
 
 			//  println!("{:?}", data);
@@ -288,18 +321,17 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::apply_jurors_helper_link(key, phase_data, who, stake)?;
 
 			Ok(())
 		}
 
+		/// Change the period manually, periods are also changed automatically in `on_initialize`
+		/// of schelling game shared when the period is over
 		#[pallet::call_index(5)]
 		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
 		pub fn pass_period(
@@ -311,13 +343,10 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 			T::SchellingGameSharedSource::change_period_link(key, phase_data, now)?;
 
 			Ok(())
@@ -335,12 +364,9 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::draw_jurors_helper_link(key, phase_data, iterations)?;
 
@@ -356,10 +382,7 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::unstaking_helper_link(key, who)?;
 			Ok(())
@@ -376,10 +399,7 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::commit_vote_for_score_helper_link(key, who, vote_commit)?;
 			Ok(())
@@ -401,41 +421,55 @@ pub mod pallet {
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate,
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
 
 			T::SchellingGameSharedSource::reveal_vote_score_helper_link(key, who, choice, salt)?;
 			Ok(())
 		}
 
+		/// Settle at most `limit` jurors of the game
+		/// Positive externality score is set by `OnGameConcluded` when settlement starts
 		#[pallet::call_index(10)]
-		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
+		#[pallet::weight(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(*limit))]
 		pub fn get_incentives(
 			origin: OriginFor<T>,
 			user_to_calculate: T::AccountId,
-		) -> DispatchResult {
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
 			let _who = ensure_signed(origin)?;
 			let pe_block_number =
 				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
 
-			let key = SumTreeName::PositiveExternality {
-				user_address: user_to_calculate.clone(),
-				block_number: pe_block_number.clone(),
-			};
+			let key = Self::game_key(user_to_calculate.clone(), pe_block_number.clone());
 
-			let phase_data = Self::get_phase_data();
-			T::SchellingGameSharedSource::get_incentives_score_schelling_helper_link(
-				key.clone(),
-				phase_data,
-				RangePoint::ZeroToFive,
-			)?;
+			let phase_data = Self::get_game_phase_data(key.clone())?;
+			let (processed, _settled) =
+				T::SchellingGameSharedSource::settle_score_schelling_helper_link(
+					key.clone(),
+					phase_data,
+					RangePoint::ZeroToFive,
+					ScoreAggregation::MeanWithinStdDev,
+					limit,
+				)?;
 
-			let score = T::SchellingGameSharedSource::get_mean_value_link(key.clone());
-			// println!("Score {:?}", score);
-			T::SharedStorageSource::set_positive_externality_link(user_to_calculate, score)?;
+			// Score is set in shared storage by `OnGameConcluded` when settlement starts
+			Ok(Some(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(processed)).into())
+		}
 
+		#[pallet::call_index(11)]
+		#[pallet::weight(Weight::from_parts(10_000, u64::MAX) + T::DbWeight::get().writes(1))]
+		pub fn appeal(origin: OriginFor<T>, user_to_calculate: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who == user_to_calculate, Error::<T>::NotAGameParty);
+
+			let pe_block_number =
+				<ValidationPositiveExternalityBlock<T>>::get(user_to_calculate.clone());
+
+			let key = Self::game_key(user_to_calculate, pe_block_number.clone());
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
+			T::SchellingGameSharedSource::appeal_helper_link(key, phase_data, who, None, now)?;
 			Ok(())
 		}
 	}
@@ -0,0 +1,86 @@
+//! Storage migrations of the positive externality validation pallet
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use pallet_support::OldContent;
+
+pub mod v1 {
+	use super::*;
+
+	/// Post before version 1, the content is not bounded
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct OldPositiveExternalityPost<T: Config> {
+		pub id: PositiveExternalityPostId,
+		pub created: WhoAndWhenOf<T>,
+		pub edited: bool,
+		pub owner: T::AccountId,
+		pub content: OldContent,
+		pub hidden: bool,
+		pub upvotes_count: u32,
+		pub downvotes_count: u32,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type PositiveExternalityPostById<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		PositiveExternalityPostId,
+		OldPositiveExternalityPost<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type PositiveExternalityEvidence<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, AccountIdOf<T>, Vec<PositiveExternalityPostId>>;
+
+	/// Bound the content of posts and the posts of a user
+	/// Content longer than `MAX_CONTENT_LENGTH` is truncated and logged, posts after the
+	/// first `MaxPostsPerUser` are no longer listed under their user
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+
+			let posts: Vec<_> = PositiveExternalityPostById::<T>::drain().collect();
+			for (post_id, post) in posts {
+				let post = PositiveExternalityPost::<T> {
+					id: post.id,
+					created: post.created,
+					edited: post.edited,
+					owner: post.owner,
+					content: post.content.into_bounded(post_id),
+					hidden: post.hidden,
+					upvotes_count: post.upvotes_count,
+					downvotes_count: post.downvotes_count,
+				};
+				<crate::PositiveExternalityPostById<T>>::insert(post_id, post);
+				reads += 1;
+				writes += 1;
+			}
+
+			let evidence: Vec<_> = PositiveExternalityEvidence::<T>::drain().collect();
+			for (user, post_ids) in evidence {
+				let post_ids = BoundedVec::<_, T::MaxPostsPerUser>::truncate_from(post_ids);
+				<crate::PositiveExternalityEvidence<T>>::insert(user, post_ids);
+				reads += 1;
+				writes += 1;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
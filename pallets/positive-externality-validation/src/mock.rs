@@ -1,11 +1,13 @@
 use crate as pallet_template;
-use frame_support::{parameter_types, traits::{ConstU16, ConstU64, GenesisBuild}};
+use frame_support::{parameter_types, traits::{ConstU16, ConstU32, ConstU64, GenesisBuild}};
+use pallet_template::PositiveExternalityKey;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
 use frame_support_test::TestRandomness;
+use schelling_game_shared::randomness::InstantRandomness;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -58,6 +60,7 @@ impl frame_system::Config for Test {
 impl shared_storage::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type MaxApprovedCitizens = ConstU32<100>;
 }
 parameter_types! {
     pub const MinimumPeriod: u64 = 5;
@@ -90,16 +93,25 @@ impl pallet_balances::Config for Test {
 impl schelling_game_shared::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = PositiveExternalityKey<u64, u64>;
 	type Currency = Balances; // New code
-	type RandomnessSource = TestRandomness<Self>;
+	type RandomnessSource = InstantRandomness<TestRandomness<Self>>;
 	type Slash = ();
 	type Reward = ();
+	type MaxPeriodChangesPerBlock = ConstU32<50>;
+	type GameRetentionPeriod = ConstU64<100>;
+	type MaxGamesPrunedPerBlock = ConstU32<50>;
+	type MaxDrawsPerRound = ConstU32<50>;
+	type MinSaltLength = ConstU32<4>;
+	type PhaseDataOrigin = frame_system::EnsureRoot<u64>;
+	type OnGameConcluded = TemplateModule;
 	type SortitionSumGameSource = SortitionSumGame;
 }
 
 impl sortition_sum_game::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = PositiveExternalityKey<u64, u64>;
 }
 
 impl pallet_template::Config for Test {
@@ -107,7 +119,9 @@ impl pallet_template::Config for Test {
 	type WeightInfo = ();
 	type SharedStorageSource = SharedStorage;
 	type Currency = Balances; // New code
+	type SchellingGameKey = PositiveExternalityKey<u64, u64>;
 	type SchellingGameSharedSource = SchellingGameShared;
+	type MaxPostsPerUser = ConstU32<2>;
 }
 
 // Build genesis storage according to the mock runtime.
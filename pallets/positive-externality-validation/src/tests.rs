@@ -1,7 +1,11 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
-use pallet_support::{Content, WhoAndWhen};
-use crate::types::PositiveExternalityPost;
+use crate::{migrations::v1, mock::*, Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use pallet_support::{Content, OldContent, WhoAndWhen};
+use crate::types::{PositiveExternalityKey, PositiveExternalityPost};
+use schelling_game_shared::types::CommitChoice;
 
 #[test]
 fn test_positive_externality_post() {
@@ -26,6 +30,58 @@ fn test_positive_externality_post() {
 	});
 }
 
+#[test]
+fn posts_of_a_user_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::create_positive_externality_post(
+			RuntimeOrigin::signed(1),
+			Content::None
+		));
+		assert_ok!(TemplateModule::create_positive_externality_post(
+			RuntimeOrigin::signed(1),
+			Content::None
+		));
+		assert_noop!(
+			TemplateModule::create_positive_externality_post(
+				RuntimeOrigin::signed(1),
+				Content::None
+			),
+			Error::<Test>::TooManyPosts
+		);
+		assert_eq!(TemplateModule::positive_externality_evidence(1).to_vec(), vec![1, 2]);
+		assert_eq!(TemplateModule::positive_externality_post_by_id(3), None);
+	});
+}
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		let post = v1::OldPositiveExternalityPost::<Test> {
+			id: 1,
+			created: WhoAndWhen { account: 1, block: 0, time: 0 },
+			edited: false,
+			owner: 1,
+			content: OldContent::IPFS(vec![b'a'; 100]),
+			hidden: false,
+			upvotes_count: 0,
+			downvotes_count: 0,
+		};
+		v1::PositiveExternalityPostById::<Test>::insert(1, post);
+		v1::PositiveExternalityEvidence::<Test>::insert(1, vec![1, 2, 3]);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		// Content longer than an IPFS CID is kept
+		assert_eq!(
+			TemplateModule::positive_externality_post_by_id(1).unwrap().content,
+			Content::IPFS(vec![b'a'; 100].try_into().unwrap())
+		);
+		assert_eq!(TemplateModule::positive_externality_evidence(1).to_vec(), vec![1, 2]);
+	});
+}
+
 #[test]
 fn test_adding_positive_externality_stake() {
 	new_test_ext().execute_with(|| {
@@ -156,16 +212,25 @@ fn test_commit_and_incentives_vote() {
 		assert_eq!(data, [(4, 1000), (5, 2000), (6, 3000), (7, 4000), (8, 5000)]);
 		assert_ok!(TemplateModule::pass_period(RuntimeOrigin::signed(4), 1));
 
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
+		let key = PositiveExternalityKey {
+			user_address: 1,
+			block_number: TemplateModule::validation_positive_externality_block_number(1),
+		};
+		let hash =
+			SchellingGameShared::commit_hash(&key, &4, &CommitChoice::Score(1), "salt".as_bytes());
 		assert_ok!(TemplateModule::commit_vote(RuntimeOrigin::signed(4), 1, hash));
 
-		let hash = sp_io::hashing::keccak_256("1salt2".as_bytes());
+		let hash =
+			SchellingGameShared::commit_hash(&key, &5, &CommitChoice::Score(1), "salt2".as_bytes());
 		assert_ok!(TemplateModule::commit_vote(RuntimeOrigin::signed(5), 1, hash));
-		let hash = sp_io::hashing::keccak_256("5salt3".as_bytes());
+		let hash =
+			SchellingGameShared::commit_hash(&key, &6, &CommitChoice::Score(5), "salt3".as_bytes());
 		assert_ok!(TemplateModule::commit_vote(RuntimeOrigin::signed(6), 1, hash));
-		let hash = sp_io::hashing::keccak_256("1salt4".as_bytes());
+		let hash =
+			SchellingGameShared::commit_hash(&key, &7, &CommitChoice::Score(1), "salt4".as_bytes());
 		assert_ok!(TemplateModule::commit_vote(RuntimeOrigin::signed(7), 1, hash));
-		let hash = sp_io::hashing::keccak_256("5salt5".as_bytes());
+		let hash =
+			SchellingGameShared::commit_hash(&key, &8, &CommitChoice::Score(5), "salt5".as_bytes());
 		assert_ok!(TemplateModule::commit_vote(RuntimeOrigin::signed(8), 1, hash));
 		System::set_block_number(12980160);
 		assert_ok!(TemplateModule::pass_period(RuntimeOrigin::signed(4), 1));
@@ -201,7 +266,12 @@ fn test_commit_and_incentives_vote() {
 		));
 		System::set_block_number(12980260);
 		assert_ok!(TemplateModule::pass_period(RuntimeOrigin::signed(4), 1));
+		System::set_block_number(12980360);
+		assert_ok!(TemplateModule::pass_period(RuntimeOrigin::signed(4), 1));
 
-		assert_ok!(TemplateModule::get_incentives(RuntimeOrigin::signed(4), 1));
+		assert_ok!(TemplateModule::get_incentives(RuntimeOrigin::signed(4), 1, 5));
+		// Score is recorded when the game concludes, scores 1, 1, 5, 1, 5 give mean 1 within std dev
+		assert_eq!(SharedStorage::positive_externality_score(1), 1000);
+		assert_ok!(TemplateModule::get_incentives(RuntimeOrigin::signed(4), 1, 5));
 	})
 }
@@ -7,9 +7,20 @@ use super::*;
 
 pub const FIRST_POST_ID: u64 = 1;
 
+/// Key of the schelling game of the positive externality of a user
+/// Converted by the runtime into its `SchellingGameKey`
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct PositiveExternalityKey<AccountId, BlockNumber> {
+    pub user_address: AccountId,
+    pub block_number: BlockNumber,
+}
+
 /// Information about a post's owner, its' related space, content, and visibility.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
 pub struct PositiveExternalityPost<T: Config> {
     pub id: PositiveExternalityPostId,
 
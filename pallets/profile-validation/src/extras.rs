@@ -52,8 +52,38 @@ impl<T: Config> ChallengeEvidencePost<T> {
 }
 
 impl<T: Config> Pallet<T> {
-	pub(super) fn get_phase_data() -> PhaseData<T> {
-		T::SchellingGameSharedSource::create_phase_data(50, 5, 3, 100, (100, 100))
+	/// Phase data set for the game type in schelling game shared, or the default of the pallet
+	pub(super) fn get_phase_data() -> Result<PhaseData<T>, DispatchError> {
+		let game_type = SchellingGameType::ProfileApproval;
+		if let Some(phase_data) = T::SchellingGameSharedSource::get_phase_data_link(game_type) {
+			return Ok(phase_data);
+		}
+		let phase_lengths = PhaseLengths {
+			evidence: 50,
+			staking: 50,
+			// Longer than the availability delay of the randomness of the runtime
+			drawing: 100,
+			commit: 50,
+			vote: 50,
+			appeal: 50,
+		};
+		T::SchellingGameSharedSource::create_phase_data(phase_lengths, 5, 3, 100, (100, 100), 2)
+	}
+
+	/// Key of the schelling game of the profile of `citizen_address` started at `block_number`
+	pub(super) fn game_key(
+		citizen_address: T::AccountId,
+		block_number: BlockNumberOf<T>,
+	) -> T::SchellingGameKey {
+		ProfileValidationKey { citizen_address, block_number }.into()
+	}
+
+	/// Phase data of the game, stored in schelling game shared when the game started
+	pub(super) fn get_game_phase_data(
+		key: T::SchellingGameKey,
+	) -> Result<PhaseData<T>, DispatchError> {
+		T::SchellingGameSharedSource::get_game_phase_data_link(key)
+			.ok_or(Error::<T>::GamePhaseDataNotFound.into())
 	}
 
 	// pub(super) fn get_citizen_accountid(
@@ -67,6 +97,25 @@ impl<T: Config> Pallet<T> {
 		PALLET_ID.into_sub_account_truncating(1)
 	}
 
+	/// Return the deposits of the funders of the profile and reset the collected fund,
+	/// so that the profile can be funded again
+	pub(super) fn refund_profile_funders(profile_user_account: T::AccountId) {
+		let fund_details = <ProfileFundDetails<T>>::drain_prefix(&profile_user_account);
+		for (funder, profile_fund_info) in fund_details {
+			if !profile_fund_info.deposit_returned {
+				let _ = <T as pallet::Config>::Currency::deposit_creating(
+					&funder,
+					profile_fund_info.deposit,
+				);
+				Self::deposit_event(Event::ProfileFundReturned {
+					profile: profile_user_account.clone(),
+					funder,
+				});
+			}
+		}
+		<ProfileTotalFundCollected<T>>::remove(&profile_user_account);
+	}
+
 	// pub fn get_challengers_evidence(
 	// 	profile_citizenid: CitizenId,
 	// 	offset: u64,
@@ -166,3 +215,28 @@ impl<T: Config> Pallet<T> {
 	// 	result
 	// }
 }
+
+impl<T: Config>
+	OnGameConcluded<
+		ProfileValidationKey<T::AccountId, BlockNumberOf<T>>,
+		GameResult<BlockNumberOf<T>>,
+	> for Pallet<T>
+{
+	/// Citizen is approved in shared storage when jurors decide in favour of the profile
+	/// Funders get their deposits back when the game is cancelled
+	fn on_game_concluded(
+		key: ProfileValidationKey<T::AccountId, BlockNumberOf<T>>,
+		result: GameResult<BlockNumberOf<T>>,
+	) -> DispatchResult {
+		match result.outcome {
+			GameOutcome::TwoChoice(WinningDecision::WinnerYes) => {
+				T::SharedStorageSource::add_approved_citizen_address_link(key.citizen_address)?;
+			},
+			GameOutcome::Undecided | GameOutcome::Cancelled => {
+				Self::refund_profile_funders(key.citizen_address);
+			},
+			_ => {},
+		}
+		Ok(())
+	}
+}
@@ -23,6 +23,7 @@ pub mod weights;
 pub use weights::*;
 
 mod extras;
+pub mod migrations;
 mod permissions;
 mod types;
 
@@ -39,29 +40,39 @@ use frame_support::{
 use pallet_support::{
 	ensure_content_is_valid, new_who_and_when, remove_from_vec, Content, WhoAndWhen, WhoAndWhenOf,
 };
-use schelling_game_shared::types::{Period, PhaseData, RangePoint, SchellingGameType};
-use schelling_game_shared_link::SchellingGameSharedLink;
-use sortition_sum_game::types::SumTreeName;
-pub use types::{CitizenDetailsPost, FIRST_CHALLENGE_POST_ID, FIRST_CITIZEN_ID};
+use schelling_game_shared::types::{
+	GameOutcome, GameResult, Period, PhaseData, PhaseLengths, RangePoint, SchellingGameType,
+	WinningDecision,
+};
+use schelling_game_shared::WeightInfo as SchellingGameWeightInfo;
+use schelling_game_shared_link::{OnGameConcluded, SchellingGameSharedLink};
+use shared_storage_link::SharedStorageLink;
+pub use types::{
+	CitizenDetailsPost, ProfileValidationKey, FIRST_CHALLENGE_POST_ID, FIRST_CITIZEN_ID,
+};
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 type ProfileFundInfoOf<T> = ProfileFundInfo<BalanceOf<T>, AccountIdOf<T>>;
 type ChallengerFundInfoOf<T> =
 	ChallengerFundInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber, AccountIdOf<T>>;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
+type SchellingGameWeight<T> = <T as schelling_game_shared::Config>::WeightInfo;
 type CitizenId = u64;
 type ChallengePostId = u64;
 
 const PALLET_ID: PalletId = PalletId(*b"ex/cfund");
 
-#[frame_support::pallet(dev_mode)]
+#[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 bounds the content of posts and the comments of challenge posts
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -74,8 +85,13 @@ pub mod pallet {
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
+		/// Key of the games of `SchellingGameSharedSource`, defined by the runtime
+		/// Games of this pallet are keyed by `ProfileValidationKey`
+		type SchellingGameKey: Parameter
+			+ From<ProfileValidationKey<Self::AccountId, Self::BlockNumber>>;
+
 		type SchellingGameSharedSource: SchellingGameSharedLink<
-			SumTreeName = SumTreeName<Self::AccountId, Self::BlockNumber>,
+			SumTreeName = Self::SchellingGameKey,
 			SchellingGameType = SchellingGameType,
 			BlockNumber = Self::BlockNumber,
 			AccountId = AccountIdOf<Self>,
@@ -83,8 +99,14 @@ pub mod pallet {
 			RangePoint = RangePoint,
 			Period = Period,
 			PhaseData = PhaseData<Self>,
+			PhaseLengths = PhaseLengths,
 		>;
+		type SharedStorageSource: SharedStorageLink<AccountId = AccountIdOf<Self>>;
 		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Maximum number of comments of a challenge post
+		#[pallet::constant]
+		type MaxChallengePostComments: Get<u32>;
 	}
 
 	// The pallet's runtime storage items.
@@ -186,8 +208,13 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn challenge_post_comment)]
-	pub type ChallengePostCommentIds<T: Config> =
-		StorageMap<_, Blake2_128Concat, ChallengePostId, Vec<ChallengePostId>, ValueQuery>; // challenge post id => Vec<Comment Post It>
+	pub type ChallengePostCommentIds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ChallengePostId,
+		BoundedVec<ChallengePostId, T::MaxChallengePostComments>,
+		ValueQuery,
+	>; // challenge post id => Vec<Comment Post It>
 
 	#[pallet::storage]
 	#[pallet::getter(fn challenge_post)]
@@ -210,6 +237,10 @@ pub mod pallet {
 			profile: T::AccountId,
 			funder: T::AccountId,
 		},
+		ProfileFundReturned {
+			profile: T::AccountId,
+			funder: T::AccountId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -234,6 +265,10 @@ pub mod pallet {
 		CitizenNotApproved,
 		NotAPostOwner,
 		AmountFundedGreaterThanRequired,
+		NotAGameParty,
+		GamePhaseDataNotFound,
+		/// Comments of the challenge post reached `MaxChallengePostComments`
+		TooManyComments,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -301,13 +336,13 @@ pub mod pallet {
 			if amount_to_fund <= required_fund {
 				if amount_to_fund == required_fund {
 					let now = <frame_system::Pallet<T>>::block_number();
-					let key = SumTreeName::ProfileValidation {
-						citizen_address: profile_user_account.clone(),
-						block_number: now.clone(),
-					};
+					let key = Self::game_key(profile_user_account.clone(), now.clone());
 					<ProfileValidationBlock<T>>::insert(&profile_user_account, now);
 
-					T::SchellingGameSharedSource::set_to_evidence_period_link(key, now)?;
+					let phase_data = Self::get_phase_data()?;
+					T::SchellingGameSharedSource::set_to_evidence_period_link(
+						key, phase_data, now,
+					)?;
 				}
 				let _ = <T as pallet::Config>::Currency::withdraw(
 					&who,
@@ -371,12 +406,9 @@ pub mod pallet {
 
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 			let now = <frame_system::Pallet<T>>::block_number();
 
 			T::SchellingGameSharedSource::set_to_staking_period_link(key.clone(), phase_data, now)?;
@@ -475,7 +507,9 @@ pub mod pallet {
 						match comment_ids.binary_search(&count) {
 							Ok(_) => Err(Error::<T>::CommentExists)?,
 							Err(index) => {
-								comment_ids.insert(index, count.clone());
+								comment_ids
+									.try_insert(index, count.clone())
+									.map_err(|_| Error::<T>::TooManyComments)?;
 								<ChallengePostCommentIds<T>>::insert(&post_id, &comment_ids);
 							},
 						}
@@ -553,6 +587,8 @@ pub mod pallet {
 		// }
 
 		// May be you need to check challeger fund details exists
+		/// Change the period manually, periods are also changed automatically in `on_initialize`
+		/// of schelling game shared when the period is over
 		#[pallet::call_index(5)]
 		#[pallet::weight(0)]
 		pub fn pass_period(origin: OriginFor<T>, profile_user_account: T::AccountId) -> DispatchResult {
@@ -561,13 +597,10 @@ pub mod pallet {
 			
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::change_period_link(key, phase_data, now)?;
 
@@ -601,12 +634,9 @@ pub mod pallet {
 
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 
-			let phase_data = Self::get_phase_data();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::apply_jurors_helper_link(key, phase_data, who, stake)?;
 
@@ -631,11 +661,8 @@ pub mod pallet {
 			let _who = ensure_signed(origin)?;
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
-			let phase_data = Self::get_phase_data();
+			let key = Self::game_key(profile_user_account.clone(), block_number);
+			let phase_data = Self::get_game_phase_data(key.clone())?;
 
 			T::SchellingGameSharedSource::draw_jurors_helper_link(key, phase_data, iterations)?;
 
@@ -651,10 +678,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 			T::SchellingGameSharedSource::unstaking_helper_link(key, who)?;
 			Ok(())
 		}
@@ -669,10 +693,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 			T::SchellingGameSharedSource::commit_vote_helper_link(key, who, vote_commit)?;
 			Ok(())
 		}
@@ -688,10 +709,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
-			};
+			let key = Self::game_key(profile_user_account.clone(), block_number);
 
 			T::SchellingGameSharedSource::reveal_vote_two_choice_helper_link(
 				key, who, choice, salt,
@@ -700,22 +718,52 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Settle at most `limit` jurors of the game
+		/// Citizen is approved by `OnGameConcluded` when settlement starts
 		#[pallet::call_index(11)]
-		#[pallet::weight(0)]
+		#[pallet::weight(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(*limit))]
 		pub fn get_incentives(
 			origin: OriginFor<T>,
-			profile_user_account: T::AccountId
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			profile_user_account: T::AccountId,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let _who = ensure_signed(origin)?;
 			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
 
-			let key = SumTreeName::ProfileValidation {
-				citizen_address: profile_user_account.clone(),
-				block_number,
+			let key = Self::game_key(profile_user_account.clone(), block_number);
+			let phase_data = Self::get_game_phase_data(key.clone())?;
+			let (processed, _settled) =
+				T::SchellingGameSharedSource::settle_two_choice_helper_link(
+					key, phase_data, limit,
+				)?;
+			Ok(Some(<SchellingGameWeight<T> as SchellingGameWeightInfo>::settle(processed)).into())
+		}
+
+		// Appeal
+		// Profile owner or challenger can appeal in appeal period if their side lost ✔️
+		// Profile owner stands for approval (1), challengers for rejection (0)
+		#[pallet::call_index(12)]
+		#[pallet::weight(0)]
+		pub fn appeal(origin: OriginFor<T>, profile_user_account: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let appellant_choice = if who == profile_user_account {
+				1
+			} else if <ChallengerEvidenceId<T>>::contains_key(&profile_user_account, &who) {
+				0
+			} else {
+				Err(Error::<T>::NotAGameParty)?
 			};
-			let phase_data = Self::get_phase_data();
-			T::SchellingGameSharedSource::get_incentives_two_choice_helper_link(
-				key, phase_data, who,
+			let block_number = <ProfileValidationBlock<T>>::get(&profile_user_account);
+
+			let key = Self::game_key(profile_user_account.clone(), block_number);
+			let now = <frame_system::Pallet<T>>::block_number();
+			let phase_data = Self::get_game_phase_data(key.clone())?;
+			T::SchellingGameSharedSource::appeal_helper_link(
+				key,
+				phase_data,
+				who,
+				Some(appellant_choice),
+				now,
 			)?;
 			Ok(())
 		}
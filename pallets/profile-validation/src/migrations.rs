@@ -0,0 +1,113 @@
+//! Storage migrations of the profile validation pallet
+
+use crate::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use pallet_support::OldContent;
+
+pub mod v1 {
+	use super::*;
+
+	/// Profile before version 1, the content is not bounded
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct OldCitizenDetailsPost<T: Config> {
+		pub created: WhoAndWhenOf<T>,
+		pub content: OldContent,
+		pub citizen_id: CitizenId,
+		pub owner: T::AccountId,
+		pub edited: bool,
+		pub hidden: bool,
+		pub upvotes_count: u32,
+		pub downvotes_count: u32,
+	}
+
+	/// Challenge post before version 1, the content is not bounded
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct OldChallengeEvidencePost<T: Config> {
+		pub created: WhoAndWhenOf<T>,
+		pub owner: T::AccountId,
+		pub kyc_profile_id: T::AccountId,
+		pub content: OldContent,
+		pub post_id_if_comment: Option<ChallengePostId>,
+		pub is_comment: bool,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type CitizenProfile<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, AccountIdOf<T>, OldCitizenDetailsPost<T>>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type ChallengePost<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, ChallengePostId, OldChallengeEvidencePost<T>>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type ChallengePostCommentIds<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, ChallengePostId, Vec<ChallengePostId>>;
+
+	/// Bound the content of profiles and challenge posts, and the comments of challenge posts
+	/// Content longer than `MAX_CONTENT_LENGTH` is truncated and logged, comments after the
+	/// first `MaxChallengePostComments` are no longer listed under their challenge post
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+
+			let profiles: Vec<_> = CitizenProfile::<T>::drain().collect();
+			for (account, profile) in profiles {
+				let profile = CitizenDetailsPost::<T> {
+					created: profile.created,
+					content: profile.content.into_bounded(&account),
+					citizen_id: profile.citizen_id,
+					owner: profile.owner,
+					edited: profile.edited,
+					hidden: profile.hidden,
+					upvotes_count: profile.upvotes_count,
+					downvotes_count: profile.downvotes_count,
+				};
+				<crate::CitizenProfile<T>>::insert(account, profile);
+				reads += 1;
+				writes += 1;
+			}
+
+			let posts: Vec<_> = ChallengePost::<T>::drain().collect();
+			for (post_id, post) in posts {
+				let post = ChallengeEvidencePost::<T> {
+					created: post.created,
+					owner: post.owner,
+					kyc_profile_id: post.kyc_profile_id,
+					content: post.content.into_bounded(post_id),
+					post_id_if_comment: post.post_id_if_comment,
+					is_comment: post.is_comment,
+				};
+				<crate::ChallengePost<T>>::insert(post_id, post);
+				reads += 1;
+				writes += 1;
+			}
+
+			let comments: Vec<_> = ChallengePostCommentIds::<T>::drain().collect();
+			for (post_id, comment_ids) in comments {
+				let comment_ids =
+					BoundedVec::<_, T::MaxChallengePostComments>::truncate_from(comment_ids);
+				<crate::ChallengePostCommentIds<T>>::insert(post_id, comment_ids);
+				reads += 1;
+				writes += 1;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
@@ -1,11 +1,13 @@
 use crate as pallet_template;
-use frame_support::{parameter_types, traits::{ConstU16, ConstU64}};
+use frame_support::{parameter_types, traits::{ConstU16, ConstU32, ConstU64}};
+use pallet_template::ProfileValidationKey;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
 use frame_support_test::TestRandomness;
+use schelling_game_shared::randomness::InstantRandomness;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -24,6 +26,7 @@ frame_support::construct_runtime!(
 		Timestamp: pallet_timestamp,
 		SchellingGameShared: schelling_game_shared,
 		SortitionSumGame: sortition_sum_game,
+		SharedStorage: shared_storage,
 	}
 );
 
@@ -87,22 +90,40 @@ impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type Currency = Balances; // New code
+	type SchellingGameKey = ProfileValidationKey<u64, u64>;
 	type SchellingGameSharedSource = SchellingGameShared;
+	type SharedStorageSource = SharedStorage;
+	type MaxChallengePostComments = ConstU32<3>;
 }
 
 impl schelling_game_shared::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = ProfileValidationKey<u64, u64>;
 	type Currency = Balances; // New code
-	type RandomnessSource = TestRandomness<Self>;
+	type RandomnessSource = InstantRandomness<TestRandomness<Self>>;
 	type Slash = ();
 	type Reward = ();
+	type MaxPeriodChangesPerBlock = ConstU32<50>;
+	type GameRetentionPeriod = ConstU64<100>;
+	type MaxGamesPrunedPerBlock = ConstU32<50>;
+	type MaxDrawsPerRound = ConstU32<50>;
+	type MinSaltLength = ConstU32<4>;
+	type PhaseDataOrigin = frame_system::EnsureRoot<u64>;
+	type OnGameConcluded = ProfileValidation;
 	type SortitionSumGameSource = SortitionSumGame;
 }
 
 impl sortition_sum_game::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = ProfileValidationKey<u64, u64>;
+}
+
+impl shared_storage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxApprovedCitizens = ConstU32<100>;
 }
 
 // Build genesis storage according to the mock runtime.
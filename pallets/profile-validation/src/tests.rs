@@ -1,10 +1,14 @@
-use crate::types::CitizenDetailsPost;
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::types::{CitizenDetailsPost, ProfileValidationKey};
+use crate::{migrations::v1, mock::*, Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 use pallet_support::Content;
-use pallet_support::WhoAndWhen;
-use schelling_game_shared::types::Period;
-use sortition_sum_game::types::SumTreeName;
+use pallet_support::{OldContent, WhoAndWhen};
+use schelling_game_shared::types::{
+	CommitChoice, GameOutcome, Period, PhaseData, PhaseLengths, SchellingGameType,
+};
 
 #[test]
 fn add_citizen_profile_check() {
@@ -14,7 +18,9 @@ fn add_citizen_profile_check() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
 		let data = ProfileValidation::citizen_profile(1);
@@ -33,7 +39,9 @@ fn add_citizen_profile_check() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqz"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
 		let data = ProfileValidation::citizen_profile(1);
@@ -59,7 +67,9 @@ fn check_fund_addition() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
 		let data = ProfileValidation::citizen_profile(1);
@@ -82,7 +92,9 @@ fn check_fund_addition() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqz"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_noop!(
 			ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()),
@@ -109,7 +121,7 @@ fn check_fund_addition() {
 		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(5), 1, 300));
 		System::assert_last_event(Event::ProfileFund { profile: 1, funder: 5 }.into());
 
-		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 10 };
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 10 };
 		let period = SchellingGameShared::get_period(key);
 		assert_eq!(Some(Period::Evidence), period);
 	})
@@ -122,21 +134,25 @@ fn challenge_evidence() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
 		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 1000));
-		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 1 };
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 1 };
 		let period = SchellingGameShared::get_period(key.clone());
 		assert_eq!(Some(Period::Evidence), period);
 
 		let challenge_content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhabc"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 
-		let phase_data = ProfileValidation::get_phase_data();
+		let phase_data = ProfileValidation::get_phase_data().unwrap();
 
 		assert_noop!(
 			ProfileValidation::challenge_profile(
@@ -174,16 +190,20 @@ fn schelling_game_test() {
 		let content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
 		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
 		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 1000));
 		let challenge_content: Content = Content::IPFS(
 			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhabc"
 				.as_bytes()
-				.to_vec(),
+				.to_vec()
+				.try_into()
+				.unwrap(),
 		);
-		let phase_data = ProfileValidation::get_phase_data();
+		let phase_data = ProfileValidation::get_phase_data().unwrap();
 		System::set_block_number(phase_data.evidence_length + 1);
 		assert_ok!(ProfileValidation::challenge_profile(
 			RuntimeOrigin::signed(4),
@@ -216,13 +236,13 @@ fn schelling_game_test() {
 
 		assert_ok!(ProfileValidation::draw_jurors(RuntimeOrigin::signed(5), 1, 5));
 
-		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 1 };
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 1 };
 
 		let draws_in_round = SchellingGameShared::draws_in_round(key.clone());
 		assert_eq!(5, draws_in_round);
 
 		let drawn_jurors = SchellingGameShared::drawn_jurors(key.clone());
-		assert_eq!(vec![(4, 400), (7, 700), (13, 1300), (14, 1400), (15, 1500)], drawn_jurors);
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
 
 		assert_ok!(ProfileValidation::pass_period(RuntimeOrigin::signed(5), 1));
 
@@ -236,29 +256,64 @@ fn schelling_game_test() {
 		let balance = Balances::free_balance(5);
 		assert_eq!(300000, balance);
 
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&6,
+			&CommitChoice::TwoChoice(1),
+			"salt".as_bytes(),
+		);
 		assert_noop!(
 			ProfileValidation::commit_vote(RuntimeOrigin::signed(6), 1, hash),
 			<schelling_game_shared::Error<Test>>::JurorDoesNotExists
 		);
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(4), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&12,
+			&CommitChoice::TwoChoice(1),
+			"salt".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(12), 1, hash));
 
 		// You can replace vote within the commit period.
-		let hash = sp_io::hashing::keccak_256("1salt2".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(4), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&12,
+			&CommitChoice::TwoChoice(1),
+			"salt2".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(12), 1, hash));
 
-		let hash = sp_io::hashing::keccak_256("1salt3".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(7), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&15,
+			&CommitChoice::TwoChoice(1),
+			"salt3".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(15), 1, hash));
 
-		let hash = sp_io::hashing::keccak_256("1salt4".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(13), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&19,
+			&CommitChoice::TwoChoice(1),
+			"salt4".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(19), 1, hash));
 
-		let hash = sp_io::hashing::keccak_256("1salt5".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(14), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&20,
+			&CommitChoice::TwoChoice(1),
+			"salt5".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(20), 1, hash));
 
-		let hash = sp_io::hashing::keccak_256("0salt6".as_bytes());
-		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(15), 1, hash));
+		let hash = SchellingGameShared::commit_hash(
+			&key,
+			&22,
+			&CommitChoice::TwoChoice(0),
+			"salt6".as_bytes(),
+		);
+		assert_ok!(ProfileValidation::commit_vote(RuntimeOrigin::signed(22), 1, hash));
 
 		assert_noop!(
 			ProfileValidation::pass_period(RuntimeOrigin::signed(5), 1),
@@ -271,7 +326,7 @@ fn schelling_game_test() {
 
 		assert_noop!(
 			ProfileValidation::reveal_vote(
-				RuntimeOrigin::signed(4),
+				RuntimeOrigin::signed(12),
 				1,
 				2,
 				"salt2".as_bytes().to_vec()
@@ -280,28 +335,28 @@ fn schelling_game_test() {
 		);
 
 		assert_ok!(ProfileValidation::reveal_vote(
-			RuntimeOrigin::signed(4),
+			RuntimeOrigin::signed(12),
 			1,
 			1,
 			"salt2".as_bytes().to_vec()
 		));
 
 		assert_ok!(ProfileValidation::reveal_vote(
-			RuntimeOrigin::signed(7),
+			RuntimeOrigin::signed(15),
 			1,
 			1,
 			"salt3".as_bytes().to_vec()
 		));
 
 		assert_ok!(ProfileValidation::reveal_vote(
-			RuntimeOrigin::signed(13),
+			RuntimeOrigin::signed(19),
 			1,
 			1,
 			"salt4".as_bytes().to_vec()
 		));
 
 		assert_ok!(ProfileValidation::reveal_vote(
-			RuntimeOrigin::signed(14),
+			RuntimeOrigin::signed(20),
 			1,
 			1,
 			"salt5".as_bytes().to_vec()
@@ -318,15 +373,194 @@ fn schelling_game_test() {
 				+ phase_data.vote_length,
 		);
 		assert_ok!(ProfileValidation::pass_period(RuntimeOrigin::signed(5), 1));
-
+		let period = SchellingGameShared::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		assert_noop!(
+			ProfileValidation::appeal(RuntimeOrigin::signed(5), 1),
+			Error::<Test>::NotAGameParty
+		);
+		// Profile was approved, the profile owner can not appeal
+		assert_noop!(
+			ProfileValidation::appeal(RuntimeOrigin::signed(1), 1),
+			<schelling_game_shared::Error<Test>>::NotLosingParty
+		);
 		assert_noop!(
-			ProfileValidation::get_incentives(RuntimeOrigin::signed(15), 1),
-			<schelling_game_shared::Error<Test>>::VoteNotRevealed
+			ProfileValidation::pass_period(RuntimeOrigin::signed(5), 1),
+			<schelling_game_shared::Error<Test>>::AppealPeriodNotOver
+		);
+		System::set_block_number(
+			phase_data.evidence_length
+				+ 1 + phase_data.staking_length
+				+ phase_data.commit_length
+				+ phase_data.vote_length
+				+ phase_data.appeal_length,
 		);
-		let balance: u64 = Balances::free_balance(14);
-		assert_eq!(300000 - 14 * 100, balance);
-		assert_ok!(ProfileValidation::get_incentives(RuntimeOrigin::signed(14), 1));
-		let balance: u64 = Balances::free_balance(14);
+		assert_ok!(ProfileValidation::pass_period(RuntimeOrigin::signed(5), 1));
+
+		let balance: u64 = Balances::free_balance(20);
+		assert_eq!(300000 - 20 * 100, balance);
+		assert_eq!(SharedStorage::approved_citizen_address(), vec![]);
+		// Anyone can settle the jurors in pages
+		assert_ok!(ProfileValidation::get_incentives(RuntimeOrigin::signed(5), 1, 5));
+		// Jurors decided in favour of the profile, citizen is approved
+		assert_eq!(SharedStorage::approved_citizen_address(), vec![1]);
+		assert_ok!(ProfileValidation::get_incentives(RuntimeOrigin::signed(5), 1, 5));
+		let balance: u64 = Balances::free_balance(20);
 		assert_eq!(300025, balance);
+		assert_noop!(
+			ProfileValidation::get_incentives(RuntimeOrigin::signed(5), 1, 5),
+			<schelling_game_shared::Error<Test>>::GameAlreadySettled
+		);
+	})
+}
+
+#[test]
+fn cancel_game_refunds_profile_funders() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		let content: Content = Content::IPFS(
+			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
+				.as_bytes()
+				.to_vec()
+				.try_into()
+				.unwrap(),
+		);
+		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content));
+		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 200));
+		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(4), 1, 800));
+
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 10 };
+		assert_ok!(SchellingGameShared::cancel_game(RuntimeOrigin::root(), key.clone()));
+		System::assert_has_event(Event::ProfileFundReturned { profile: 1, funder: 4 }.into());
+		assert_eq!(Balances::free_balance(3), 300000);
+		assert_eq!(Balances::free_balance(4), 300000);
+		assert!(ProfileValidation::profile_fund_details(1, 3).is_none());
+		assert_eq!(ProfileValidation::total_fund_for_profile_collected(1), 0);
+		assert_eq!(SchellingGameShared::game_result(key).unwrap().outcome, GameOutcome::Cancelled);
+
+		// Profile can be funded again
+		System::set_block_number(20);
+		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 1000));
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 20 };
+		assert_eq!(SchellingGameShared::get_period(key), Some(Period::Evidence));
+	})
+}
+
+#[test]
+fn game_uses_phase_data_of_its_start() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let content: Content = Content::IPFS(
+			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
+				.as_bytes()
+				.to_vec()
+				.try_into()
+				.unwrap(),
+		);
+		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
+		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 1000));
+		let key = ProfileValidationKey { citizen_address: 1, block_number: 1 };
+		let phase_data = ProfileValidation::get_phase_data().unwrap();
+		assert_eq!(SchellingGameShared::game_phase_data(key.clone()), Some(phase_data.clone()));
+
+		let new_phase_data = PhaseData::<Test>::create_with_data(
+			PhaseLengths::uniform(500),
+			7,
+			10,
+			500,
+			(100, 200),
+			2,
+		);
+		assert_ok!(SchellingGameShared::set_phase_data(
+			RuntimeOrigin::root(),
+			SchellingGameType::ProfileApproval,
+			new_phase_data.clone()
+		));
+		assert_eq!(ProfileValidation::get_phase_data().unwrap(), new_phase_data);
+
+		System::set_block_number(phase_data.evidence_length + 1);
+		assert_ok!(ProfileValidation::challenge_profile(RuntimeOrigin::signed(4), 1, content));
+		assert_eq!(SchellingGameShared::get_period(key.clone()), Some(Period::Staking));
+		assert_ok!(ProfileValidation::apply_jurors(RuntimeOrigin::signed(5), 1, 100));
+		assert_eq!(SchellingGameShared::game_phase_data(key), Some(phase_data));
+	})
+}
+
+#[test]
+fn challenge_comments_are_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let content: Content = Content::IPFS(
+			"bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
+				.as_bytes()
+				.to_vec()
+				.try_into()
+				.unwrap(),
+		);
+		assert_ok!(ProfileValidation::add_citizen(RuntimeOrigin::signed(1), content.clone()));
+		assert_ok!(ProfileValidation::add_profile_stake(RuntimeOrigin::signed(3), 1, 1000));
+		let phase_data = ProfileValidation::get_phase_data().unwrap();
+		System::set_block_number(phase_data.evidence_length + 1);
+		assert_ok!(ProfileValidation::challenge_profile(
+			RuntimeOrigin::signed(4),
+			1,
+			content.clone()
+		));
+
+		for who in 5..8 {
+			assert_ok!(ProfileValidation::challenge_comment_create(
+				RuntimeOrigin::signed(who),
+				1,
+				content.clone()
+			));
+		}
+		assert_eq!(ProfileValidation::challenge_post_comment(1).into_inner(), vec![2, 3, 4]);
+		assert_noop!(
+			ProfileValidation::challenge_comment_create(RuntimeOrigin::signed(8), 1, content),
+			Error::<Test>::TooManyComments
+		);
 	})
 }
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<ProfileValidation>();
+		let cid = "bafkreiaiq24be2iioasr6ftyaum3icmj7amtjkom2jeokov5k5ojwzhvqy"
+			.as_bytes()
+			.to_vec();
+		let created = WhoAndWhen { account: 1, block: 1, time: 0 };
+		let profile = v1::OldCitizenDetailsPost::<Test> {
+			created: created.clone(),
+			content: OldContent::IPFS(cid.clone()),
+			citizen_id: 1,
+			owner: 1,
+			edited: false,
+			hidden: false,
+			upvotes_count: 0,
+			downvotes_count: 0,
+		};
+		v1::CitizenProfile::<Test>::insert(1, profile);
+		let post = v1::OldChallengeEvidencePost::<Test> {
+			created,
+			owner: 4,
+			kyc_profile_id: 1,
+			content: OldContent::IPFS(vec![1; 1100]),
+			post_id_if_comment: None,
+			is_comment: false,
+		};
+		v1::ChallengePost::<Test>::insert(1, post);
+		v1::ChallengePostCommentIds::<Test>::insert(1, vec![2, 3, 4, 5]);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(ProfileValidation::on_chain_storage_version(), 1);
+		let profile = ProfileValidation::citizen_profile(1).unwrap();
+		assert_eq!(profile.content, Content::IPFS(cid.try_into().unwrap()));
+		// Content longer than `MAX_CONTENT_LENGTH` is truncated
+		assert_eq!(
+			ProfileValidation::challenge_post(1).unwrap().content,
+			Content::IPFS(vec![1; 1024].try_into().unwrap())
+		);
+		assert_eq!(ProfileValidation::challenge_post_comment(1).into_inner(), vec![2, 3, 4]);
+	});
+}
@@ -8,8 +8,9 @@ pub const FIRST_CITIZEN_ID: CitizenId  = 1;
 pub const FIRST_CHALLENGE_POST_ID: ChallengePostId = 1;
 
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
 pub struct CitizenDetailsPost<T: Config> {
     pub created: WhoAndWhenOf<T>,
     pub content: Content,
@@ -22,6 +23,16 @@ pub struct CitizenDetailsPost<T: Config> {
 }
 
 
+/// Key of the schelling game of a profile
+/// Converted by the runtime into its `SchellingGameKey`
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct ProfileValidationKey<AccountId, BlockNumber> {
+    pub citizen_address: AccountId,
+    pub block_number: BlockNumber,
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Default, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct ProfileFundInfo<Balance, AccountId> {
@@ -40,8 +51,9 @@ pub struct ChallengerFundInfo<Balance, BlockNumber, AccountId> {
     pub challenge_completed: bool,
 }
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
 pub struct ChallengeEvidencePost<T: Config> {
     pub created: WhoAndWhenOf<T>,
     pub owner: T::AccountId,
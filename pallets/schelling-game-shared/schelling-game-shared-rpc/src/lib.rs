@@ -0,0 +1,118 @@
+use codec::{Codec, Decode, Encode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+pub use schelling_game_shared_runtime_api::SchellingGameSharedApi as SchellingGameSharedRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait SchellingGameSharedApi<BlockHash, AccountId> {
+	/// `key` and `choice` are the SCALE encoded game key of the runtime and `CommitChoice`
+	#[method(name = "schellinggameshared_commithash")]
+	fn commit_hash(
+		&self,
+		key: Bytes,
+		who: AccountId,
+		choice: Bytes,
+		salt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<H256>;
+
+	#[method(name = "schellinggameshared_minsaltlength")]
+	fn min_salt_length(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// SCALE encoded `JurorStats` of the account
+	#[method(name = "schellinggameshared_jurorstats")]
+	fn juror_stats(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// A struct that implements the `SchellingGameSharedApi`.
+pub struct SchellingGameShared<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> SchellingGameShared<C, M> {
+	/// Create new `SchellingGameShared` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn decode_param<T: Decode>(name: &str, bytes: &Bytes) -> RpcResult<T> {
+	T::decode(&mut &bytes[..]).map_err(|e| {
+		CallError::Custom(ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			format!("Unable to decode {}", name),
+			Some(format!("{:?}", e)),
+		))
+		.into()
+	})
+}
+
+fn runtime_error<E: std::fmt::Debug>(e: E) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		9876, // No real reason for this value
+		"Something wrong",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+impl<C, Block, SumTreeName, AccountId, CommitChoice, JurorStats>
+	SchellingGameSharedApiServer<<Block as BlockT>::Hash, AccountId>
+	for SchellingGameShared<C, (Block, SumTreeName, CommitChoice, JurorStats)>
+where
+	Block: BlockT,
+	SumTreeName: Codec + Send + Sync + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+	CommitChoice: Codec + Send + Sync + 'static,
+	JurorStats: Codec + Send + Sync + 'static,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: SchellingGameSharedRuntimeApi<Block, SumTreeName, AccountId, CommitChoice, JurorStats>,
+{
+	fn commit_hash(
+		&self,
+		key: Bytes,
+		who: AccountId,
+		choice: Bytes,
+		salt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<H256> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let key: SumTreeName = decode_param("key", &key)?;
+		let choice: CommitChoice = decode_param("choice", &choice)?;
+		api.commit_hash(at, key, who, choice, salt.to_vec())
+			.map(H256::from)
+			.map_err(runtime_error)
+	}
+
+	fn min_salt_length(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.min_salt_length(at).map_err(runtime_error)
+	}
+
+	fn juror_stats(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Bytes> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.juror_stats(at, who)
+			.map(|stats| stats.encode().into())
+			.map_err(runtime_error)
+	}
+}
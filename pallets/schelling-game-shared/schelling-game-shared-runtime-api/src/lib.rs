@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_api::codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait SchellingGameSharedApi<SumTreeName, AccountId, CommitChoice, JurorStats>
+	where
+		SumTreeName: Codec,
+		AccountId: Codec,
+		CommitChoice: Codec,
+		JurorStats: Codec,
+	{
+		/// Commit hash of the vote, same as the hash checked on reveal
+		fn commit_hash(key: SumTreeName, who: AccountId, choice: CommitChoice, salt: Vec<u8>) -> [u8; 32];
+		/// Minimum length of the salt of a commit
+		fn min_salt_length() -> u32;
+		/// Settlement history of the juror: games drawn, committed, revealed, coherent, slashed
+		/// amount and rewards earned
+		fn juror_stats(who: AccountId) -> JurorStats;
+	}
+}
@@ -0,0 +1,125 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Number of jurors drawn in a round
+	/// First round draws `max_draws`, every appeal draws `2n + 1` jurors of the previous round
+	pub(super) fn get_max_draws_for_round(phase_data: &PhaseDataOf<T>, round: u32) -> u64 {
+		let mut max_draws = phase_data.max_draws;
+		for _ in 0..round {
+			max_draws = max_draws.saturating_mul(2).saturating_add(1);
+		}
+		max_draws
+	}
+
+	/// Appeal the ruling of the current round
+	/// Ensure `Period` is `Appeal` and appeal period is not over
+	/// `appellant_choice` is the choice the appellant stands for, two choice (`0` or `1`) or the
+	/// option of a multi choice game, the appeal is rejected if that choice won the round
+	/// `None` for games without sides, the consumer pallet restricts who can appeal
+	/// Take the appeal fee, move drawn jurors to `PastRoundDrawnJurors`
+	/// and start a new round from `Period::Staking`
+	pub(super) fn appeal_helper(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		who: AccountIdOf<T>,
+		appellant_choice: Option<u32>,
+		now: BlockNumberOf<T>,
+	) -> DispatchResult {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Appeal, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		let appeal_start_time = <AppealStartTime<T>>::get(&key);
+		ensure!(now < phase_data.appeal_length + appeal_start_time, Error::<T>::AppealPeriodOver);
+		let round = <AppealRound<T>>::get(&key);
+		ensure!(round < phase_data.max_appeals, Error::<T>::MaxAppealsReached);
+		if let Some(choice) = appellant_choice {
+			ensure!(!Self::choice_won_round(&key, choice), Error::<T>::NotLosingParty);
+		}
+
+		let imbalance = T::Currency::withdraw(
+			&who,
+			phase_data.appeal_fee,
+			WithdrawReasons::FEE,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		T::Slash::on_unbalanced(imbalance);
+
+		let drawn_jurors = <DrawnJurors<T>>::get(&key);
+		<PastRoundDrawnJurors<T>>::insert(&key, round, drawn_jurors);
+		<DrawnJurors<T>>::remove(&key);
+		<DrawsInRound<T>>::remove(&key);
+		<DecisionCount<T>>::remove(&key);
+		<RevealScoreValues<T>>::remove(&key);
+		<RevealScoreStakes<T>>::remove(&key);
+		let _ = <MultiChoiceCount<T>>::clear_prefix(&key, u32::MAX, None);
+
+		<CommitRedraws<T>>::remove(&key);
+		<AppealRound<T>>::insert(&key, round + 1);
+		Self::deposit_event(Event::Appealed { key: key.clone(), who, round: round + 1 });
+		<PeriodName<T>>::insert(&key, Period::Staking);
+		<StakingStartTime<T>>::insert(&key, now);
+		Self::schedule_period_change(
+			key.clone(),
+			phase_data.clone(),
+			now.saturating_add(phase_data.staking_length),
+		);
+		Self::deposit_event(Event::PeriodChanged { key, period: Period::Staking });
+
+		Ok(())
+	}
+
+	/// `choice` won the current round, nobody wins a draw
+	/// Only one of `DecisionCount` and `MultiChoiceCount` is counted in a game, the other is a draw
+	fn choice_won_round(key: &SumTreeNameType<T>, choice: u32) -> bool {
+		let two_choice_won = match Self::get_winning_decision(<DecisionCount<T>>::get(key)) {
+			WinningDecision::WinnerYes => choice == 1,
+			WinningDecision::WinnerNo => choice == 0,
+			WinningDecision::Draw => false,
+		};
+		let multi_choice_won = match Self::get_winning_option(key.clone()) {
+			WinningOption::Winner(option) => option == choice,
+			WinningOption::Draw => false,
+		};
+		two_choice_won || multi_choice_won
+	}
+
+	/// Drawn jurors of every round of the game, current round included
+	pub(super) fn get_all_rounds_drawn_jurors(
+		key: SumTreeNameType<T>,
+	) -> Vec<(AccountIdOf<T>, u64)> {
+		let mut drawn_jurors = <DrawnJurors<T>>::get(&key).into_inner();
+		for past_drawn_jurors in <PastRoundDrawnJurors<T>>::iter_prefix_values(&key) {
+			drawn_jurors.extend(past_drawn_jurors);
+		}
+		drawn_jurors.extend(<ReplacedJurors<T>>::get(&key));
+		drawn_jurors
+	}
+
+	/// Juror was drawn in an appealed round, or was replaced for not committing
+	pub(super) fn drawn_in_previous_round(key: SumTreeNameType<T>, who: AccountIdOf<T>) -> bool {
+		<PastRoundDrawnJurors<T>>::iter_prefix_values(&key)
+			.any(|drawn_jurors| drawn_jurors.binary_search_by(|(c, _)| c.cmp(&who)).is_ok())
+			|| <ReplacedJurors<T>>::get(&key).iter().any(|(c, _)| *c == who)
+	}
+
+	pub(super) fn get_appeal_period_end_block_helper(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		now: BlockNumberOf<T>,
+	) -> Option<u32> {
+		let appeal_start_time = <AppealStartTime<T>>::get(&key);
+		let appeal_length = phase_data.appeal_length;
+		let end_block = appeal_start_time.checked_add(&appeal_length).expect("Overflow");
+		let left_block = end_block.checked_sub(&now);
+		match left_block {
+			Some(val) => {
+				let left_block_u32 = Self::block_number_to_u32_saturated(val);
+				Some(left_block_u32)
+			},
+			None => Some(0),
+		}
+	}
+}
@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Domain of the commit hash, it separates the commits from other keccak hashes
+pub const COMMIT_DOMAIN: &[u8] = b"schelling-game-shared/commit";
+
+/// Version of the commit format, changed whenever the preimage of the commit changes
+pub const COMMIT_VERSION: u8 = 1;
+
+impl<T: Config> Pallet<T> {
+	/// Commit hash of a vote
+	/// `keccak_256(SCALE(COMMIT_DOMAIN, COMMIT_VERSION, key, who, choice, salt))`
+	/// Clients must use the same encoding to build the commit, it is also available through the
+	/// runtime api and rpc
+	pub fn commit_hash(
+		key: &SumTreeNameType<T>,
+		who: &AccountIdOf<T>,
+		choice: &CommitChoice,
+		salt: &[u8],
+	) -> [u8; 32] {
+		let preimage = (COMMIT_DOMAIN, COMMIT_VERSION, key, who, choice, salt).encode();
+		sp_io::hashing::keccak_256(&preimage)
+	}
+
+	/// Minimum length of the salt of a commit
+	pub fn min_salt_length() -> u32 {
+		T::MinSaltLength::get()
+	}
+
+	/// Check the salt length and that the revealed choice matches the commit of the juror
+	pub(super) fn ensure_commit_matches(
+		key: &SumTreeNameType<T>,
+		who: &AccountIdOf<T>,
+		choice: &CommitChoice,
+		salt: &[u8],
+		commit: &[u8; 32],
+	) -> DispatchResult {
+		ensure!(salt.len() >= T::MinSaltLength::get() as usize, Error::<T>::SaltTooShort);
+		let hash = Self::commit_hash(key, who, choice, salt);
+		ensure!(&hash == commit, Error::<T>::CommitDoesNotMatch);
+		Ok(())
+	}
+}
@@ -3,24 +3,34 @@ use core::cmp::min;
 use crate::*;
 
 impl<T: Config> Pallet<T> {
+	/// Phase data with a length for each period
+	/// Returns `InvalidPhaseData` if a length or `max_draws`, `min_number_juror_staked` or
+	/// `min_juror_stake` is zero, or the last of `max_appeals` rounds draws more than
+	/// `MaxDrawsPerRound` jurors
 	pub(super) fn create_phase_data(
-		block_length: u64,
+		phase_lengths: PhaseLengths,
 		max_draws: u64,
 		min_number_juror_staked: u64,
 		min_juror_stake: u64,
 		juror_incentives: (u64, u64),
-	) -> PhaseDataOf<T> {
-		PhaseData::create_with_data(
-			block_length,
+		max_appeals: u32,
+	) -> Result<PhaseDataOf<T>, DispatchError> {
+		let phase_data = PhaseData::create_with_data(
+			phase_lengths,
 			max_draws,
 			min_number_juror_staked,
 			min_juror_stake,
 			juror_incentives,
-		)
+			max_appeals,
+		);
+		ensure!(phase_data.is_valid(), Error::<T>::InvalidPhaseData);
+		Ok(phase_data)
 	}
 	/// Set to evidence period, when some one stakes for validation
+	/// `phase_data` is stored as the phase data of the game
 	pub(super) fn set_to_evidence_period(
 		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
 		now: BlockNumberOf<T>,
 	) -> DispatchResult {
 		match <PeriodName<T>>::get(&key) {
@@ -29,6 +39,8 @@ impl<T: Config> Pallet<T> {
 				let period = Period::Evidence;
 				<PeriodName<T>>::insert(&key, period);
 				<EvidenceStartTime<T>>::insert(&key, now);
+				<GamePhaseData<T>>::insert(&key, phase_data);
+				Self::deposit_event(Event::PeriodChanged { key, period: Period::Evidence });
 			},
 		}
 		Ok(())
@@ -56,6 +68,12 @@ impl<T: Config> Pallet<T> {
 				let new_period = Period::Staking;
 				<PeriodName<T>>::insert(&key, new_period);
 				<StakingStartTime<T>>::insert(&key, now);
+				Self::schedule_period_change(
+					key.clone(),
+					phase_data.clone(),
+					now.saturating_add(phase_data.staking_length),
+				);
+				Self::deposit_event(Event::PeriodChanged { key, period: Period::Staking });
 			} else {
 				Err(Error::<T>::EvidencePeriodNotOver)?
 			}
@@ -69,12 +87,19 @@ impl<T: Config> Pallet<T> {
 	/// Set staking period for no evidence period
 	pub(super) fn set_to_staking_period_pe(
 		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
 		now: BlockNumberOf<T>,
 	) -> DispatchResult {
 		if let None = <PeriodName<T>>::get(&key) {
 			let new_period = Period::Staking;
 			<PeriodName<T>>::insert(&key, new_period);
 			<StakingStartTime<T>>::insert(&key, now);
+			Self::schedule_period_change(
+				key.clone(),
+				phase_data.clone(),
+				now.saturating_add(phase_data.staking_length),
+			);
+			Self::deposit_event(Event::PeriodChanged { key, period: Period::Staking });
 		} else {
 			Err(Error::<T>::PeriodIsNotNone)?
 		}
@@ -99,7 +124,13 @@ impl<T: Config> Pallet<T> {
 	/// ```
 	///
 	///  `Period::Drawing` to `Period::Commit`   
-	/// When maximum juror are drawn   
+	/// When maximum juror are drawn, or when drawing period is over and at least `min_draws`
+	/// jurors are drawn   
+	/// ```ignore
+	/// if now >= drawing_length + drawing_start_time && draws_in_round < min_draws {
+	///   // Cancel the game, stakes of drawn jurors are refunded
+	/// }
+	/// ```
 	///  
 	/// `Period::Commit` to `Period::Vote`       
 	/// ```ignore
@@ -107,13 +138,25 @@ impl<T: Config> Pallet<T> {
 	///   // Change `Period::Commit` to `Period::Vote`  
 	/// }
 	/// ```
+	/// If some drawn jurors did not commit and `max_commit_redraws` is not reached,
+	/// they are replaced and `Period::Commit` goes back to `Period::Drawing`
 	///
-	/// `Period::Vote` to `Period::Execution`   
+	/// `Period::Vote` to `Period::Appeal`, or to `Period::Execution` when `max_appeals` is reached   
 	/// ```ignore
 	/// if now >= min_long_block_length + vote_start_time {
-	///   // Change `Period::Vote` to `Period::Execution`   
+	///   // Change `Period::Vote` to `Period::Appeal`   
 	/// }
 	/// ```
+	///
+	/// `Period::Appeal` to `Period::Execution`   
+	/// ```ignore
+	/// if now >= appeal_length + appeal_start_time {
+	///   // Change `Period::Appeal` to `Period::Execution`   
+	/// }
+	/// ```
+	///
+	/// The next period change is scheduled at the end of the new period,
+	/// and done automatically in `on_initialize`
 	pub(super) fn change_period(
 		key: SumTreeNameType<T>,
 		phase_data: PhaseDataOf<T>,
@@ -122,26 +165,53 @@ impl<T: Config> Pallet<T> {
 		match <PeriodName<T>>::get(&key) {
 			Some(period) => {
 				match period {
-					Period::Evidence => todo!(),
+					Period::Evidence => Err(Error::<T>::PeriodDontMatch)?,
 					Period::Staking => {
 						// Also check has min number of jurors has staked
 						let staking_start_time = <StakingStartTime<T>>::get(&key);
 						let staking_length = phase_data.staking_length;
 						if now >= staking_length + staking_start_time {
+							<DrawSeedBlock<T>>::insert(&key, T::RandomnessSource::seed_block(now));
+							<DrawSeed<T>>::remove(&key);
+							<DrawingStartTime<T>>::insert(&key, now);
 							let new_period = Period::Drawing;
 							<PeriodName<T>>::insert(&key, new_period);
+							Self::schedule_period_change(
+								key.clone(),
+								phase_data.clone(),
+								now.saturating_add(phase_data.drawing_length),
+							);
+							Self::deposit_event(Event::PeriodChanged {
+								key: key.clone(),
+								period: Period::Drawing,
+							});
 						} else {
 							Err(Error::<T>::StakingPeriodNotOver)?
 						}
 					},
 					Period::Drawing => {
-						// Also give time
-						let max_draws = phase_data.max_draws;
+						let round = <AppealRound<T>>::get(&key);
+						let max_draws = Self::get_max_draws_for_round(&phase_data, round);
 						let draws_in_round = <DrawsInRound<T>>::get(&key);
-						if draws_in_round >= max_draws {
+						let drawing_start_time = <DrawingStartTime<T>>::get(&key);
+						let drawing_over = now >= phase_data.drawing_length + drawing_start_time;
+						if draws_in_round >= max_draws
+							|| (drawing_over && draws_in_round >= phase_data.min_draws)
+						{
 							<CommitStartTime<T>>::insert(&key, now);
 							let new_period = Period::Commit;
 							<PeriodName<T>>::insert(&key, new_period);
+							Self::schedule_period_change(
+								key.clone(),
+								phase_data.clone(),
+								now.saturating_add(phase_data.commit_length),
+							);
+							Self::deposit_event(Event::PeriodChanged {
+								key: key.clone(),
+								period: Period::Commit,
+							});
+						} else if drawing_over {
+							Self::cancel_game_with_outcome(key.clone(), GameOutcome::Undecided)?;
 						} else {
 							Err(Error::<T>::MaxJurorNotDrawn)?
 						}
@@ -149,10 +219,39 @@ impl<T: Config> Pallet<T> {
 					Period::Commit => {
 						let commit_start_time = <CommitStartTime<T>>::get(&key);
 						let commit_length = phase_data.commit_length;
-						if now >= commit_length + commit_start_time {
+						let redraws = <CommitRedraws<T>>::get(&key);
+						if now >= commit_length + commit_start_time
+							&& redraws < phase_data.max_commit_redraws
+							&& Self::replace_jurors_without_commit(key.clone())
+						{
+							<CommitRedraws<T>>::insert(&key, redraws + 1);
+							<DrawSeedBlock<T>>::insert(&key, T::RandomnessSource::seed_block(now));
+							<DrawSeed<T>>::remove(&key);
+							<DrawingStartTime<T>>::insert(&key, now);
+							let new_period = Period::Drawing;
+							<PeriodName<T>>::insert(&key, new_period);
+							Self::schedule_period_change(
+								key.clone(),
+								phase_data.clone(),
+								now.saturating_add(phase_data.drawing_length),
+							);
+							Self::deposit_event(Event::PeriodChanged {
+								key: key.clone(),
+								period: Period::Drawing,
+							});
+						} else if now >= commit_length + commit_start_time {
 							<VoteStartTime<T>>::insert(&key, now);
 							let new_period = Period::Vote;
 							<PeriodName<T>>::insert(&key, new_period);
+							Self::schedule_period_change(
+								key.clone(),
+								phase_data.clone(),
+								now.saturating_add(phase_data.vote_length),
+							);
+							Self::deposit_event(Event::PeriodChanged {
+								key: key.clone(),
+								period: Period::Vote,
+							});
 						} else {
 							Err(Error::<T>::CommitPeriodNotOver)?
 						}
@@ -161,14 +260,47 @@ impl<T: Config> Pallet<T> {
 						let vote_start_time = <VoteStartTime<T>>::get(&key);
 						let vote_length = phase_data.vote_length;
 						if now >= vote_length + vote_start_time {
+							let round = <AppealRound<T>>::get(&key);
+							if round < phase_data.max_appeals {
+								<AppealStartTime<T>>::insert(&key, now);
+								let new_period = Period::Appeal;
+								<PeriodName<T>>::insert(&key, new_period);
+								Self::schedule_period_change(
+									key.clone(),
+									phase_data.clone(),
+									now.saturating_add(phase_data.appeal_length),
+								);
+								Self::deposit_event(Event::PeriodChanged {
+									key: key.clone(),
+									period: Period::Appeal,
+								});
+							} else {
+								let new_period = Period::Execution;
+								<PeriodName<T>>::insert(&key, new_period);
+								Self::deposit_event(Event::PeriodChanged {
+									key: key.clone(),
+									period: Period::Execution,
+								});
+							}
+						} else {
+							Err(Error::<T>::VotePeriodNotOver)?
+						}
+					},
+					Period::Appeal => {
+						let appeal_start_time = <AppealStartTime<T>>::get(&key);
+						let appeal_length = phase_data.appeal_length;
+						if now >= appeal_length + appeal_start_time {
 							let new_period = Period::Execution;
 							<PeriodName<T>>::insert(&key, new_period);
+							Self::deposit_event(Event::PeriodChanged {
+								key: key.clone(),
+								period: Period::Execution,
+							});
 						} else {
-							Err(Error::<T>::VotePeriodNotOver)?
+							Err(Error::<T>::AppealPeriodNotOver)?
 						}
 					},
-					Period::Appeal => todo!(),
-					Period::Execution => todo!(),
+					Period::Execution => Err(Error::<T>::PeriodDontMatch)?,
 				}
 			},
 			None => Err(Error::<T>::PeriodDoesNotExists)?,
@@ -191,20 +323,12 @@ impl<T: Config> Pallet<T> {
 		let min_stake = phase_data.min_juror_stake;
 
 		ensure!(stake >= min_stake, Error::<T>::JurorStakeLessThanMin);
-
-		// let imb = T::Currency::withdraw(
-		// 	&who,
-		// 	stake,
-		// 	WithdrawReasons::TRANSFER,
-		// 	ExistenceRequirement::AllowDeath,
-		// )?;
-
-		// T::Currency::resolve_creating(&Self::juror_stake_account(), imb);
-
-		let imbalance = T::Currency::slash(&who, stake).0;
-		T::Slash::on_unbalanced(imbalance);
-
-		// let stake_of = Self::stake_of(key.clone(), profile_citizenid)?;
+		ensure!(
+			!Self::drawn_in_previous_round(key.clone(), who.clone()),
+			Error::<T>::DrawnInPreviousRound
+		);
+		// Jurors who unstaked in an earlier round can not stake again in an appeal round
+		ensure!(!<UnstakedJurors<T>>::contains_key(&key, &who), Error::<T>::AlreadyUnstaked);
 
 		let stake_u64 = Self::balance_to_u64_saturated(stake);
 
@@ -213,8 +337,11 @@ impl<T: Config> Pallet<T> {
 		match stake_of {
 			Some(_stake) => Err(Error::<T>::AlreadyStaked)?,
 			None => {
-				let result = T::SortitionSumGameSource::set_link(key, stake_u64, who);
-				result
+				// Stake is locked in reserve until the juror unstakes or gets the incentives
+				T::Currency::reserve(&who, stake)?;
+				T::SortitionSumGameSource::set_link(key.clone(), stake_u64, who.clone())?;
+				Self::deposit_event(Event::JurorStaked { key, who, stake });
+				Ok(())
 			},
 		}
 	}
@@ -231,38 +358,59 @@ impl<T: Config> Pallet<T> {
 			},
 			None => Err(Error::<T>::PeriodDoesNotExists)?,
 		}
-		let max_draws = phase_data.max_draws;
+		let round = <AppealRound<T>>::get(&key);
+		let max_draws = Self::get_max_draws_for_round(&phase_data, round);
 		let draws_in_round = <DrawsInRound<T>>::get(&key);
 		ensure!(draws_in_round < max_draws.into(), Error::<T>::MaxDrawExceeded);
-		let mut end_index = draws_in_round + iterations;
-		if draws_in_round + iterations >= max_draws {
-			end_index = max_draws;
-		}
-		let mut draw_increment = draws_in_round.clone();
-
-		for _ in draws_in_round..end_index {
-			let nonce = Self::get_and_increment_nonce();
-			let random_seed = T::RandomnessSource::random(&nonce).encode();
-			let random_number = u64::decode(&mut random_seed.as_ref())
-				.expect("secure hashes should always be bigger than u64; qed");
-			// let mut rng = rand::thread_rng();
-			// let random_number: u64 = rng.gen();
-			// log::info!("Random number: {:?}", random_number);
-			let accountid = T::SortitionSumGameSource::draw_link(key.clone(), random_number)?;
-			let stake = T::SortitionSumGameSource::stake_of_link(key.clone(), accountid.clone())?;
-
-			let mut drawn_juror = <DrawnJurors<T>>::get(&key);
-			match drawn_juror.binary_search_by(|(c, _)| c.cmp(&accountid)) {
+		let count = iterations.min(max_draws - draws_in_round);
+		// The seed is fixed by the first draw of the drawing period, later draws of the period
+		// derive their seed from it and the number of draws, so it does not change with the block
+		let period_seed = match <DrawSeed<T>>::get(&key) {
+			Some(seed) => seed,
+			None => {
+				let seed_block = <DrawSeedBlock<T>>::get(&key);
+				let nonce = Self::get_and_increment_nonce();
+				let random_seed = T::RandomnessSource::random(&nonce, seed_block)
+					.ok_or(Error::<T>::RandomnessNotAvailable)?;
+				let seed = sp_io::hashing::blake2_256(&random_seed.encode());
+				<DrawSeed<T>>::insert(&key, seed);
+				seed
+			},
+		};
+		let seed = if draws_in_round == 0 {
+			period_seed
+		} else {
+			sp_io::hashing::blake2_256(&(period_seed, draws_in_round).encode())
+		};
+
+		// Jurors drawn in one call are distinct, set their stake to zero so that they are
+		// not drawn again in a later call
+		let drawn = T::SortitionSumGameSource::draw_many_link(key.clone(), count, seed)?;
+		let mut drawn_jurors = <DrawnJurors<T>>::get(&key);
+		let mut draw_increment = draws_in_round;
+		for (accountid, stake) in drawn {
+			match drawn_jurors.binary_search_by(|(c, _)| c.cmp(&accountid)) {
 				Ok(_) => {},
 				Err(index) => {
-					drawn_juror.insert(index, (accountid.clone(), stake.unwrap()));
-					<DrawnJurors<T>>::insert(&key, drawn_juror);
+					drawn_jurors
+						.try_insert(index, (accountid.clone(), stake))
+						.map_err(|_| Error::<T>::TooManyJurors)?;
 					draw_increment = draw_increment + 1;
-					// println!("draw_increment, {:?}", draw_increment);
-					T::SortitionSumGameSource::set_link(key.clone(), 0, accountid)?;
+					T::SortitionSumGameSource::set_link(key.clone(), 0, accountid.clone())?;
+					Self::deposit_event(Event::JurorDrawn {
+						key: key.clone(),
+						who: accountid,
+						stake,
+					});
 				},
 			}
-			<DrawsInRound<T>>::insert(&key, draw_increment);
+		}
+		<DrawnJurors<T>>::insert(&key, drawn_jurors);
+		<DrawsInRound<T>>::insert(&key, draw_increment);
+		if draw_increment >= max_draws {
+			// Commit period starts from next block
+			let next_block = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+			Self::schedule_period_change(key, phase_data, next_block);
 		}
 		Ok(())
 	}
@@ -292,31 +440,23 @@ impl<T: Config> Pallet<T> {
 		match stake_of {
 			Some(stake) => {
 				let balance = Self::u64_to_balance_saturated(stake);
-				let mut unstaked_jurors = <UnstakedJurors<T>>::get(&key);
-				match unstaked_jurors.binary_search(&who) {
-					Ok(_) => Err(Error::<T>::AlreadyUnstaked)?,
-					Err(index) => {
-						unstaked_jurors.insert(index, who.clone());
-						<UnstakedJurors<T>>::insert(&key, unstaked_jurors);
-						// let _ = T::Currency::resolve_into_existing(
-						// 	&who,
-						// 	T::Currency::withdraw(
-						// 		&Self::juror_stake_account(),
-						// 		balance,
-						// 		WithdrawReasons::TRANSFER,
-						// 		ExistenceRequirement::AllowDeath,
-						// 	)?,
-						// );
-						let r = T::Currency::deposit_into_existing(&who, balance).ok().unwrap();
-						T::Reward::on_unbalanced(r);
-					},
-				}
+				ensure!(
+					!<UnstakedJurors<T>>::contains_key(&key, &who),
+					Error::<T>::AlreadyUnstaked
+				);
+				<UnstakedJurors<T>>::insert(&key, &who, ());
+				// Remove the stake from the tree, so that it is not drawn in an appeal round
+				T::SortitionSumGameSource::set_link(key.clone(), 0, who.clone())?;
+				T::Currency::unreserve(&who, balance);
+				Self::deposit_event(Event::JurorUnstaked {
+					key: key.clone(),
+					who: who.clone(),
+					stake: balance,
+				});
 			},
 			None => Err(Error::<T>::StakeDoesNotExists)?,
 		}
 
-		// println!("stakeof {:?}", stake_of);
-
 		Ok(())
 	}
 
@@ -340,6 +480,7 @@ impl<T: Config> Pallet<T> {
 					revealed_vote: None,
 				};
 				<VoteCommits<T>>::insert(&key, &who, vote_commit_struct);
+				Self::deposit_event(Event::VoteCommitted { key: key.clone(), who: who.clone() });
 			},
 			Err(_) => Err(Error::<T>::JurorDoesNotExists)?,
 		}
@@ -365,31 +506,37 @@ impl<T: Config> Pallet<T> {
 					commit_struct.votestatus == VoteStatus::Commited,
 					Error::<T>::VoteStatusNotCommited
 				);
-				let mut vote = format!("{}", choice).as_bytes().to_vec();
-				// let mut vote = choice.clone();
-				let mut salt_a = salt.clone();
-				vote.append(&mut salt_a);
-				let vote_bytes: &[u8] = &vote;
-				let hash = sp_io::hashing::keccak_256(vote_bytes);
-				let commit: &[u8] = &commit_struct.commit;
-				if hash == commit {
-					let mut decision_tuple = <DecisionCount<T>>::get(&key);
-					if choice == 1 {
-						decision_tuple.1 = decision_tuple.1 + 1;
-						<DecisionCount<T>>::insert(&key, decision_tuple);
-						commit_struct.revealed_vote = Some(RevealedVote::Yes);
-					} else if choice == 0 {
-						decision_tuple.0 = decision_tuple.0 + 1;
-						<DecisionCount<T>>::insert(&key, decision_tuple);
-						commit_struct.revealed_vote = Some(RevealedVote::No);
-					} else {
-						Err(Error::<T>::NotValidChoice)?
-					}
-					commit_struct.votestatus = VoteStatus::Revealed;
-					<VoteCommits<T>>::insert(&key, &who, commit_struct);
+				// Commits of appealed rounds can not be revealed in the current round
+				ensure!(
+					Self::selected_as_juror_helper(key.clone(), who.clone()),
+					Error::<T>::JurorDoesNotExists
+				);
+				Self::ensure_commit_matches(
+					&key,
+					&who,
+					&CommitChoice::TwoChoice(choice),
+					&salt,
+					&commit_struct.commit,
+				)?;
+				let mut decision_tuple = <DecisionCount<T>>::get(&key);
+				if choice == 1 {
+					decision_tuple.1 = decision_tuple.1 + 1;
+					<DecisionCount<T>>::insert(&key, decision_tuple);
+					commit_struct.revealed_vote = Some(RevealedVote::Yes);
+				} else if choice == 0 {
+					decision_tuple.0 = decision_tuple.0 + 1;
+					<DecisionCount<T>>::insert(&key, decision_tuple);
+					commit_struct.revealed_vote = Some(RevealedVote::No);
 				} else {
-					Err(Error::<T>::CommitDoesNotMatch)?
+					Err(Error::<T>::NotValidChoice)?
 				}
+				commit_struct.votestatus = VoteStatus::Revealed;
+				<VoteCommits<T>>::insert(&key, &who, commit_struct);
+				Self::deposit_event(Event::VoteRevealed {
+					key: key.clone(),
+					who: who.clone(),
+					choice,
+				});
 			},
 			None => Err(Error::<T>::CommitDoesNotExists)?,
 		}
@@ -397,269 +544,153 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Distribute incentives in a single go.
-	pub(super) fn get_all_incentives_two_choice_helper(
+	pub(super) fn getting_incentives_draw(
 		key: SumTreeNameType<T>,
-		phase_data: PhaseDataOf<T>,
+		who: AccountIdOf<T>,
+		stake: u64,
 	) -> DispatchResult {
-		match <PeriodName<T>>::get(&key) {
-			Some(period) => {
-				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
-			},
-			None => Err(Error::<T>::PeriodDoesNotExists)?,
-		}
-
-		let drawn_jurors = <DrawnJurors<T>>::get(&key);
-		let reveal_votes_iterator = <VoteCommits<T>>::iter_prefix(&key);
-
-		let mut reveal_votes = reveal_votes_iterator
-			.map(|(account_id, commit_vote)| (account_id, commit_vote.revealed_vote))
-			.collect::<Vec<(_, _)>>();
-		reveal_votes.sort_by(|a, b| a.0.cmp(&b.0));
-		let decision_count = <DecisionCount<T>>::get(&key);
-		let incentives = phase_data.juror_incentives;
-		let (winning_decision, winning_incentives) =
-			Self::get_winning_incentives(decision_count, incentives);
-		for juror in drawn_jurors {
-			match reveal_votes.binary_search_by(|(c, _)| c.cmp(&juror.0)) {
-				Ok(index) => {
-					let account_n_vote = reveal_votes[index].clone();
-					if let Some(vote) = account_n_vote.1 {
-						match winning_decision {
-							WinningDecision::WinnerYes => match vote {
-								RevealedVote::Yes => {
-									let result = Self::winner_getting_incentives2(
-										juror.0.clone(),
-										winning_incentives,
-										juror.1,
-									)?;
-									result
-								},
-								RevealedVote::No => {
-									let result =
-										Self::looser_getting_incentives2(juror.0.clone(), juror.1)?;
-									result
-								},
-							},
-							WinningDecision::WinnerNo => match vote {
-								RevealedVote::Yes => {
-									let result =
-										Self::looser_getting_incentives2(juror.0.clone(), juror.1)?;
-									result
-								},
-								RevealedVote::No => {
-									let result = Self::winner_getting_incentives2(
-										juror.0.clone(),
-										winning_incentives,
-										juror.1,
-									)?;
-									result
-								},
-							},
-							WinningDecision::Draw => {
-								let result =
-									Self::getting_incentives_draw2(juror.0.clone(), juror.1)?;
-								result
-							},
-						}
-					}
-				},
-				Err(_) => {},
-			}
-		}
-		// Remove SorititionSumTrees in `sortition-sum-game` pallet
-		let _result = T::SortitionSumGameSource::remove_tree_link(key.clone());
-
-		// Remove DrawnJurors
-		<DrawnJurors<T>>::remove(&key);
-
-		// Remove VoteCommits
-		<VoteCommits<T>>::remove_prefix(key.clone(), None); // Deprecated: Use clear_prefix instead
-													// let reveal_votes_iterator2 = <VoteCommits<T>>::iter_prefix(&key);
-													// reveal_votes_iterator2.for_each(|(account_id, _)|{
-													// 	<VoteCommits<T>>::remove(key.clone(), account_id);
-													// });
+		Self::pay_incentives(key, who, stake, 0, false);
 
 		Ok(())
 	}
 
-	// Improvements: Will it be better to distribute all jurors incentives in single call
-	pub(super) fn get_incentives_two_choice_helper(
+	pub(super) fn looser_getting_incentives(
 		key: SumTreeNameType<T>,
-		phase_data: PhaseDataOf<T>,
+		phase_data: &PhaseDataOf<T>,
 		who: AccountIdOf<T>,
+		stake: u64,
 	) -> DispatchResult {
-		match <PeriodName<T>>::get(&key) {
-			Some(period) => {
-				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
-			},
-			None => Err(Error::<T>::PeriodDoesNotExists)?,
-		}
-
-		let drawn_juror = <DrawnJurors<T>>::get(&key);
+		Self::looser_slash(key, phase_data, who, stake, None);
 
-		let who_commit_vote = <VoteCommits<T>>::get(&key, &who);
-		match who_commit_vote {
-			Some(commit_struct) => {
-				let vote_option = commit_struct.revealed_vote;
-				match vote_option {
-					Some(vote) => {
-						let decision_count = <DecisionCount<T>>::get(&key);
-						let incentives = phase_data.juror_incentives;
-						let (winning_decision, winning_incentives) =
-							Self::get_winning_incentives(decision_count, incentives);
-						if let Ok(i) = drawn_juror.binary_search_by(|(c, _)| c.cmp(&who.clone())) {
-							let stake = drawn_juror[i].1;
-							match winning_decision {
-								WinningDecision::WinnerYes => match vote {
-									RevealedVote::Yes => {
-										let result = Self::winner_getting_incentives(
-											key.clone(),
-											who.clone(),
-											winning_incentives,
-											stake,
-										)?;
-										result
-									},
-									RevealedVote::No => {
-										let result = Self::looser_getting_incentives(
-											key.clone(),
-											who.clone(),
-											stake,
-										)?;
-										result
-									},
-								},
-								WinningDecision::WinnerNo => match vote {
-									RevealedVote::Yes => {
-										let result = Self::looser_getting_incentives(
-											key.clone(),
-											who.clone(),
-											stake,
-										)?;
-										result
-									},
-									RevealedVote::No => {
-										let result = Self::winner_getting_incentives(
-											key.clone(),
-											who.clone(),
-											winning_incentives,
-											stake,
-										)?;
-										result
-									},
-								},
-								WinningDecision::Draw => {
-									let result = Self::getting_incentives_draw(
-										key.clone(),
-										who.clone(),
-										stake.clone(),
-									)?;
-									result
-								},
-							}
-						} else {
-							Err(Error::<T>::StakeDoesNotExists)?
-						}
-					},
-					None => Err(Error::<T>::VoteNotRevealed)?,
-				}
-			},
-			None => Err(Error::<T>::CommitDoesNotExists)?,
-		}
 		Ok(())
 	}
 
-	pub(super) fn getting_incentives_draw(
+	pub(super) fn winner_getting_incentives(
 		key: SumTreeNameType<T>,
 		who: AccountIdOf<T>,
+		winning_incentives: u64,
 		stake: u64,
 	) -> DispatchResult {
-		let balance = Self::u64_to_balance_saturated(stake);
-		let mut juror_got_incentives = <JurorsIncentiveDistributedAccounts<T>>::get(&key);
-		match juror_got_incentives.binary_search(&who) {
-			Ok(_) => Err(Error::<T>::AlreadyGotIncentives)?,
-			Err(index) => {
-				juror_got_incentives.insert(index, who.clone());
-				<JurorsIncentiveDistributedAccounts<T>>::insert(&key, juror_got_incentives);
-				let r = T::Currency::deposit_into_existing(&who, balance).ok().unwrap();
-				T::Reward::on_unbalanced(r);
-			},
-		}
+		Self::pay_incentives(key, who, stake, winning_incentives, true);
 
 		Ok(())
 	}
 
-	pub(super) fn getting_incentives_draw2(who: AccountIdOf<T>, stake: u64) -> DispatchResult {
-		let balance = Self::u64_to_balance_saturated(stake);
-
-		let r = T::Currency::deposit_into_existing(&who, balance).ok().unwrap();
+	/// Release the reserved stake of the juror, deposit `incentives` and emit `IncentivesPaid`
+	/// `coherent` if the juror voted for the decision of the game
+	pub(super) fn pay_incentives(
+		key: SumTreeNameType<T>,
+		who: AccountIdOf<T>,
+		stake: u64,
+		incentives: u64,
+		coherent: bool,
+	) {
+		let stake_balance = Self::u64_to_balance_saturated(stake);
+		T::Currency::unreserve(&who, stake_balance);
+		let incentives_balance = Self::u64_to_balance_saturated(incentives);
+		let r = T::Currency::deposit_into_existing(&who, incentives_balance).ok().unwrap();
 		T::Reward::on_unbalanced(r);
-
-		Ok(())
+		Self::record_juror_settlement(&who, None, coherent, 0, incentives);
+		Self::deposit_event(Event::IncentivesPaid {
+			key,
+			who,
+			amount: stake_balance + incentives_balance,
+		});
 	}
 
-	pub(super) fn looser_getting_incentives(
-		key: SumTreeNameType<T>,
+	/// Slash `amount` from the reserved stake of the juror and release the rest of the stake
+	/// Slashed balance goes to `Slash`, unless it is `redistributed` to the winners
+	/// Returns the slashed balance
+	pub(super) fn slash_reserved_stake(
 		who: AccountIdOf<T>,
 		stake: u64,
-	) -> DispatchResult {
-		let balance = Self::u64_to_balance_saturated(stake * 3 / 4);
-		let mut juror_got_incentives = <JurorsIncentiveDistributedAccounts<T>>::get(&key);
-		match juror_got_incentives.binary_search(&who) {
-			Ok(_) => Err(Error::<T>::AlreadyGotIncentives)?,
-			Err(index) => {
-				juror_got_incentives.insert(index, who.clone());
-				<JurorsIncentiveDistributedAccounts<T>>::insert(&key, juror_got_incentives);
-				let r = T::Currency::deposit_into_existing(&who, balance).ok().unwrap();
-				T::Reward::on_unbalanced(r);
-			},
+		amount: u64,
+		redistributed: bool,
+	) -> BalanceOf<T> {
+		let slash_balance = Self::u64_to_balance_saturated(amount);
+		let (imbalance, _) = T::Currency::slash_reserved(&who, slash_balance);
+		if !redistributed {
+			T::Slash::on_unbalanced(imbalance);
 		}
-		Ok(())
+		let returned = Self::u64_to_balance_saturated(stake.saturating_sub(amount));
+		T::Currency::unreserve(&who, returned);
+		slash_balance
 	}
 
-	pub(super) fn looser_getting_incentives2(who: AccountIdOf<T>, stake: u64) -> DispatchResult {
-		let balance = Self::u64_to_balance_saturated(stake * 3 / 4);
-
-		let r = T::Currency::deposit_into_existing(&who, balance).ok().unwrap();
-		T::Reward::on_unbalanced(r);
+	/// Stake slashed from a juror who voted against the decision
+	/// `distance` from the aggregated score scales the slash in score schelling game
+	pub(super) fn loser_slash_amount(
+		phase_data: &PhaseDataOf<T>,
+		stake: u64,
+		distance: Option<i64>,
+	) -> u64 {
+		let reward_curve = &phase_data.reward_curve;
+		let full_slash = reward_curve.loser_slash.mul_ceil(stake);
+		match distance {
+			Some(distance) if reward_curve.max_distance > 0 => {
+				let distance = distance.abs().min(reward_curve.max_distance);
+				(full_slash as u128 * distance as u128 / reward_curve.max_distance as u128) as u64
+			},
+			_ => full_slash,
+		}
+	}
 
-		Ok(())
+	/// Incentives of a juror who voted for the decision
+	/// With `stake_weighted` reward curve, winners share the winner incentives and the stake
+	/// slashed from losers pro rata to their stake, otherwise winner incentives are shared equally
+	pub(super) fn winner_incentives(
+		phase_data: &PhaseDataOf<T>,
+		winners_incentives: &WinnersIncentives,
+		stake: u64,
+	) -> u64 {
+		let winner_mint = phase_data.juror_incentives.1;
+		if phase_data.reward_curve.stake_weighted {
+			let pot = winner_mint.saturating_add(winners_incentives.slashed_stake) as u128;
+			(pot * stake as u128)
+				.checked_div(winners_incentives.winners_stake as u128)
+				.unwrap_or(0) as u64
+		} else {
+			winner_mint.checked_div(winners_incentives.winners_count).unwrap_or(0)
+		}
 	}
 
-	pub(super) fn winner_getting_incentives(
+	/// Slash the stake of the juror who voted against the decision as set by `reward_curve`
+	/// of `PhaseData` and return the rest
+	pub(super) fn looser_slash(
 		key: SumTreeNameType<T>,
+		phase_data: &PhaseDataOf<T>,
 		who: AccountIdOf<T>,
-		winning_incentives: u64,
 		stake: u64,
-	) -> DispatchResult {
-		let mut juror_got_incentives = <JurorsIncentiveDistributedAccounts<T>>::get(&key);
-		match juror_got_incentives.binary_search(&who) {
-			Ok(_) => Err(Error::<T>::AlreadyGotIncentives)?,
-			Err(index) => {
-				juror_got_incentives.insert(index, who.clone());
-				<JurorsIncentiveDistributedAccounts<T>>::insert(&key, juror_got_incentives);
-				let total_incentives = stake.checked_add(winning_incentives).expect("overflow");
-				let incentives = Self::u64_to_balance_saturated(total_incentives);
-				let r = T::Currency::deposit_into_existing(&who, incentives).ok().unwrap();
-				T::Reward::on_unbalanced(r);
-			},
-		};
-
-		Ok(())
+		distance: Option<i64>,
+	) {
+		let slash = Self::loser_slash_amount(phase_data, stake, distance);
+		let amount = Self::slash_reserved_stake(
+			who.clone(),
+			stake,
+			slash,
+			phase_data.reward_curve.stake_weighted,
+		);
+		Self::record_juror_settlement(&who, None, false, slash, 0);
+		Self::deposit_event(Event::JurorSlashed { key, who, amount });
 	}
 
-	pub(super) fn winner_getting_incentives2(
+	/// Slash the stake of a drawn juror who did not commit or reveal the vote
+	/// Part of the stake slashed is set by `non_commit_penalty` and `non_reveal_penalty` of `PhaseData`
+	pub(super) fn non_participation_penalty(
+		key: SumTreeNameType<T>,
+		phase_data: &PhaseDataOf<T>,
 		who: AccountIdOf<T>,
-		winning_incentives: u64,
 		stake: u64,
-	) -> DispatchResult {
-		let total_incentives = stake.checked_add(winning_incentives).expect("overflow");
-		let incentives = Self::u64_to_balance_saturated(total_incentives);
-		let r = T::Currency::deposit_into_existing(&who, incentives).ok().unwrap();
-		T::Reward::on_unbalanced(r);
-
-		Ok(())
+		reason: NonParticipation,
+	) {
+		let penalty = match reason {
+			NonParticipation::NotCommitted => phase_data.non_commit_penalty,
+			NonParticipation::NotRevealed => phase_data.non_reveal_penalty,
+		};
+		let slash = penalty.mul_floor(stake);
+		let amount = Self::slash_reserved_stake(who.clone(), stake, slash, false);
+		Self::record_juror_settlement(&who, Some(reason.clone()), false, slash, 0);
+		Self::deposit_event(Event::NonParticipationPenalty { key, who, reason, amount });
 	}
 
 	pub(super) fn get_winning_decision(decision_tuple: (u64, u64)) -> WinningDecision {
@@ -672,26 +703,6 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	pub(super) fn get_winning_incentives(
-		decision_tuple: (u64, u64),
-		incentive_tuple: (u64, u64),
-	) -> (WinningDecision, u64) {
-		let winning_decision = Self::get_winning_decision(decision_tuple);
-		match winning_decision {
-			WinningDecision::WinnerYes => {
-				let winning_incentives =
-					(incentive_tuple.1).checked_div(decision_tuple.1).expect("Overflow");
-				(WinningDecision::WinnerYes, winning_incentives)
-			},
-			WinningDecision::WinnerNo => {
-				let winning_incentives =
-					(incentive_tuple.1).checked_div(decision_tuple.0).expect("Overflow");
-				(WinningDecision::WinnerNo, winning_incentives)
-			},
-			WinningDecision::Draw => (WinningDecision::Draw, 0),
-		}
-	}
-
 	pub(super) fn balance_to_u64_saturated(input: BalanceOf<T>) -> u64 {
 		input.saturated_into::<u64>()
 	}
@@ -751,7 +762,8 @@ impl<T: Config> Pallet<T> {
 		key: SumTreeNameType<T>,
 		phase_data: PhaseDataOf<T>,
 	) -> (u64, u64, bool) {
-		let max_draws = phase_data.max_draws;
+		let round = <AppealRound<T>>::get(&key);
+		let max_draws = Self::get_max_draws_for_round(&phase_data, round);
 		let draws_in_round = <DrawsInRound<T>>::get(&key);
 		if draws_in_round >= max_draws.into() {
 			(max_draws, draws_in_round, true)
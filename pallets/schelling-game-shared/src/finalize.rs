@@ -0,0 +1,264 @@
+use crate::*;
+use frame_support::storage::with_storage_layer;
+
+impl<T: Config> Pallet<T> {
+	/// Record the `GameResult` when the game is decided and call `OnGameConcluded`
+	/// Does nothing if the result is already recorded, so the hook is called once per game
+	/// `OnGameConcluded` runs in its own storage layer, if it fails its changes are reverted and
+	/// `GameConcludedHookFailed` is emitted, the game is settled anyway
+	pub(super) fn conclude_game(
+		key: SumTreeNameType<T>,
+		outcome: GameOutcome,
+		aggregated_score: Option<i64>,
+	) {
+		if <GameResults<T>>::contains_key(&key) {
+			return;
+		}
+		let started_at = if <EvidenceStartTime<T>>::contains_key(&key) {
+			<EvidenceStartTime<T>>::get(&key)
+		} else {
+			<StakingStartTime<T>>::get(&key)
+		};
+		let game_result = GameResult {
+			outcome,
+			aggregated_score,
+			decided_at: <frame_system::Pallet<T>>::block_number(),
+			rounds: <AppealRound<T>>::get(&key).saturating_add(1),
+			juror_count: Self::get_all_rounds_drawn_jurors(key.clone()).len() as u32,
+			started_at,
+			settled_at: None,
+		};
+		<GameResults<T>>::insert(&key, game_result.clone());
+		let hook_result =
+			with_storage_layer(|| T::OnGameConcluded::on_game_concluded(key.clone(), game_result));
+		if let Err(error) = hook_result {
+			Self::deposit_event(Event::GameConcludedHookFailed { key, error });
+		}
+	}
+
+	/// All drawn jurors of the game are settled
+	pub(super) fn game_settled(key: &SumTreeNameType<T>) -> bool {
+		<GameResults<T>>::get(key).map_or(false, |result| result.settled_at.is_some())
+	}
+
+	/// Finalize the game once all jurors are settled
+	/// Removes the drawn jurors of all rounds, sets `settled_at` of the `GameResult` and schedules
+	/// pruning of the rest of the game storage after `GameRetentionPeriod`
+	/// The sortition sum tree is kept while jurors who were not drawn still have stakes in it,
+	/// they are refunded with `refund_stakes`
+	pub(super) fn finalize_game(key: SumTreeNameType<T>) {
+		// Remove SorititionSumTrees in `sortition-sum-game` pallet once it has no stakes
+		let no_stakes = T::SortitionSumGameSource::stakes_link(key.clone(), 1)
+			.map_or(true, |stakes| stakes.is_empty());
+		if no_stakes {
+			let _result = T::SortitionSumGameSource::remove_tree_link(key.clone());
+		}
+
+		// Remove DrawnJurors
+		<DrawnJurors<T>>::remove(&key);
+		let _ = <PastRoundDrawnJurors<T>>::clear_prefix(&key, u32::MAX, None);
+		<ReplacedJurors<T>>::remove(&key);
+		<DrawSeedBlock<T>>::remove(&key);
+		<DrawSeed<T>>::remove(&key);
+
+		Self::mark_game_settled(key);
+	}
+
+	/// Cancel a game that is not decided yet
+	/// The `GameResult` is recorded with `GameOutcome::Cancelled`
+	/// Used by consumer pallets through `cancel_game_link` and by the `cancel_game` extrinsic
+	/// Returns the number of refunded jurors
+	pub(super) fn cancel_game_helper(key: SumTreeNameType<T>) -> Result<u32, DispatchError> {
+		ensure!(<PeriodName<T>>::contains_key(&key), Error::<T>::PeriodDoesNotExists);
+		ensure!(!<GameResults<T>>::contains_key(&key), Error::<T>::GameAlreadyConcluded);
+		Self::cancel_game_with_outcome(key, GameOutcome::Cancelled)
+	}
+
+	/// Most refunds of `cancel_game_with_outcome`
+	/// Draws of all rounds are less than twice the draws of the last round, which are at most
+	/// `MaxDrawsPerRound`, with at most `MaxDrawsPerRound` replaced jurors and a page of
+	/// `MaxDrawsPerRound` stakers
+	pub(super) fn max_cancel_refunds() -> u32 {
+		T::MaxDrawsPerRound::get().saturating_mul(4)
+	}
+
+	/// Cancel the game before a decision
+	/// The `GameResult` is recorded with `outcome`, stakes of the drawn jurors of all rounds and
+	/// of up to `MaxDrawsPerRound` jurors left in the sortition sum tree are refunded, the rest
+	/// are refunded with `refund_stakes`, and the period is set to `Execution`
+	/// Runs in a storage layer, so nothing is changed if it fails
+	/// Returns the number of refunded jurors
+	pub(super) fn cancel_game_with_outcome(
+		key: SumTreeNameType<T>,
+		outcome: GameOutcome,
+	) -> Result<u32, DispatchError> {
+		with_storage_layer(|| {
+			Self::conclude_game(key.clone(), outcome, None);
+
+			let drawn_jurors = Self::get_all_rounds_drawn_jurors(key.clone());
+			let mut refunded = drawn_jurors.len() as u32;
+			for (juror, stake) in drawn_jurors {
+				let balance = Self::u64_to_balance_saturated(stake);
+				T::Currency::unreserve(&juror, balance);
+			}
+			refunded = refunded
+				.saturating_add(Self::refund_stakers(key.clone(), T::MaxDrawsPerRound::get()));
+
+			<DrawnJurors<T>>::remove(&key);
+			let _ = <PastRoundDrawnJurors<T>>::clear_prefix(&key, u32::MAX, None);
+			<ReplacedJurors<T>>::remove(&key);
+			<DrawSeedBlock<T>>::remove(&key);
+			<DrawSeed<T>>::remove(&key);
+			<GameSettlement<T>>::remove(&key);
+
+			<PeriodName<T>>::insert(&key, Period::Execution);
+			Self::deposit_event(Event::PeriodChanged {
+				key: key.clone(),
+				period: Period::Execution,
+			});
+			Self::deposit_event(Event::GameCancelled { key: key.clone() });
+			Self::mark_game_settled(key);
+			Ok(refunded)
+		})
+	}
+
+	/// Refund at most `limit` jurors left in the sortition sum tree of a cancelled or settled game
+	/// Refunded jurors are removed from the tree, the tree is removed once it has no stakes
+	/// There is no tree before staking
+	/// Returns the number of refunded jurors
+	pub(super) fn refund_stakers(key: SumTreeNameType<T>, limit: u32) -> u32 {
+		let stakes = T::SortitionSumGameSource::stakes_link(key.clone(), limit).unwrap_or_default();
+		let refunded = stakes.len() as u32;
+		for (juror, stake) in stakes {
+			let _ = T::SortitionSumGameSource::set_link(key.clone(), 0, juror.clone());
+			let balance = Self::u64_to_balance_saturated(stake);
+			T::Currency::unreserve(&juror, balance);
+			Self::deposit_event(Event::JurorUnstaked {
+				key: key.clone(),
+				who: juror,
+				stake: balance,
+			});
+		}
+		if refunded < limit {
+			let _ = T::SortitionSumGameSource::remove_tree_link(key);
+		}
+		refunded
+	}
+
+	/// Refund at most `limit` jurors left in the tree of a settled game, cancelled games are
+	/// settled when they are cancelled
+	/// `GameResults` and the tree are not pruned, so stakes can be refunded after pruning
+	pub(super) fn refund_stakes_helper(
+		key: SumTreeNameType<T>,
+		limit: u32,
+	) -> Result<u32, DispatchError> {
+		ensure!(Self::game_settled(&key), Error::<T>::GameNotSettled);
+		Ok(Self::refund_stakers(key, limit))
+	}
+
+	/// Set `settled_at` of the `GameResult` and schedule pruning of the game storage
+	/// after `GameRetentionPeriod`
+	fn mark_game_settled(key: SumTreeNameType<T>) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		<GameResults<T>>::mutate(&key, |result| {
+			if let Some(result) = result {
+				result.settled_at = Some(now);
+			}
+		});
+
+		let prune_at = now.saturating_add(T::GameRetentionPeriod::get().max(One::one()));
+		Self::schedule_pruning(key, prune_at);
+	}
+
+	/// Schedule pruning of the game storage at block `prune_at`
+	fn schedule_pruning(key: SumTreeNameType<T>, mut prune_at: BlockNumberOf<T>) {
+		// At most `MaxGamesPrunedPerBlock` games are pruned in a block
+		while <PruneQueue<T>>::try_append(prune_at, &key).is_err() {
+			prune_at = prune_at.saturating_add(One::one());
+		}
+	}
+
+	/// Most entries of the per juror storage removed when a game is pruned in a block
+	/// The storage has about one entry per drawn juror in each map, see `max_cancel_refunds`
+	pub(super) fn max_pruned_entries() -> u32 {
+		T::MaxDrawsPerRound::get().saturating_mul(4)
+	}
+
+	/// Prune the storage of games whose retention period ends at block `now`
+	/// A game with more than `max_pruned_entries` entries is pruned again in a later block
+	pub(super) fn prune_games(now: BlockNumberOf<T>) -> Weight {
+		let keys = <PruneQueue<T>>::take(now);
+		let count = keys.len() as u32;
+		let mut entries = 0u32;
+		for key in keys {
+			let (removed, pruned) =
+				Self::prune_game_storage(key.clone(), Self::max_pruned_entries());
+			entries = entries.saturating_add(removed);
+			if !pruned {
+				Self::schedule_pruning(key, now.saturating_add(One::one()));
+			}
+		}
+		T::WeightInfo::prune_games(count, entries)
+	}
+
+	/// Remove the per game storage, `GameResults` is kept
+	/// Vote commits of all rounds are kept until the game is pruned, as settlement reads them
+	/// At most `limit` entries of the per juror storage are removed, the rest of the storage is
+	/// removed once they are all removed
+	/// Returns the number of removed entries and whether the game storage is removed
+	pub(super) fn prune_game_storage(key: SumTreeNameType<T>, limit: u32) -> (u32, bool) {
+		let mut remaining = limit;
+		let pruned = Self::clear_game_prefix(&mut remaining, |limit| {
+			<VoteCommits<T>>::clear_prefix(&key, limit, None)
+		}) && Self::clear_game_prefix(&mut remaining, |limit| {
+			<ScoreVoteCommits<T>>::clear_prefix(&key, limit, None)
+		}) && Self::clear_game_prefix(&mut remaining, |limit| {
+			<MultiChoiceVoteCommits<T>>::clear_prefix(&key, limit, None)
+		}) && Self::clear_game_prefix(&mut remaining, |limit| {
+			<UnstakedJurors<T>>::clear_prefix(&key, limit, None)
+		}) && Self::clear_game_prefix(&mut remaining, |limit| {
+			<JurorsIncentiveDistributedAccounts<T>>::clear_prefix(&key, limit, None)
+		}) && Self::clear_game_prefix(&mut remaining, |limit| {
+			<MultiChoiceCount<T>>::clear_prefix(&key, limit, None)
+		});
+		let removed = limit.saturating_sub(remaining);
+		if !pruned {
+			return (removed, false);
+		}
+
+		<PeriodName<T>>::remove(&key);
+		<DrawsInRound<T>>::remove(&key);
+		<EvidenceStartTime<T>>::remove(&key);
+		<StakingStartTime<T>>::remove(&key);
+		<DrawingStartTime<T>>::remove(&key);
+		<CommitStartTime<T>>::remove(&key);
+		<VoteStartTime<T>>::remove(&key);
+		<AppealStartTime<T>>::remove(&key);
+		<AppealRound<T>>::remove(&key);
+		<CommitRedraws<T>>::remove(&key);
+		<GamePhaseData<T>>::remove(&key);
+		<DecisionCount<T>>::remove(&key);
+		<RevealScoreValues<T>>::remove(&key);
+		<RevealScoreStakes<T>>::remove(&key);
+		<IncentiveMeanRevealScore<T>>::remove(&key);
+		<MultiChoiceOptions<T>>::remove(&key);
+		(removed, true)
+	}
+
+	/// Clear at most `remaining` entries of a prefix with `clear`, `remaining` is reduced by the
+	/// entries visited
+	/// Returns whether the prefix is cleared
+	/// A prefix that is not cleared is cleared again in a later block, as entries removed in
+	/// the block are still visited by `clear_prefix` without a cursor
+	fn clear_game_prefix(
+		remaining: &mut u32,
+		clear: impl FnOnce(u32) -> sp_io::MultiRemovalResults,
+	) -> bool {
+		if *remaining == 0 {
+			return false;
+		}
+		let result = clear(*remaining);
+		*remaining = remaining.saturating_sub(result.loops);
+		result.maybe_cursor.is_none()
+	}
+}
@@ -15,9 +15,16 @@ impl<T: Config> PhaseData<T> {
 		vote_length: T::BlockNumber,
 		appeal_length: T::BlockNumber,
 		max_draws: u64,
+		min_draws: u64,
 		min_number_juror_staked: u64,
 		min_juror_stake: BalanceOf<T>,
 		juror_incentives: (u64, u64),
+		max_appeals: u32,
+		appeal_fee: BalanceOf<T>,
+		non_commit_penalty: Percent,
+		non_reveal_penalty: Percent,
+		max_commit_redraws: u32,
+		reward_curve: RewardCurve,
 	) -> Self {
 		PhaseData {
 			evidence_length,
@@ -27,9 +34,16 @@ impl<T: Config> PhaseData<T> {
 			vote_length,
 			appeal_length,
 			max_draws,
+			min_draws,
 			min_number_juror_staked,
 			min_juror_stake,
 			juror_incentives,
+			max_appeals,
+			appeal_fee,
+			non_commit_penalty,
+			non_reveal_penalty,
+			max_commit_redraws,
+			reward_curve,
 		}
 	}
 
@@ -42,32 +56,101 @@ impl<T: Config> PhaseData<T> {
 			vote_length: 144000u64.saturated_into::<BlockNumberOf<T>>(),
 			appeal_length: 144000u64.saturated_into::<BlockNumberOf<T>>(),
 			max_draws: 30,
+			min_draws: 16,
 			min_number_juror_staked: 50,
 			min_juror_stake: 1000u64.saturated_into::<BalanceOf<T>>(),
 			juror_incentives: (1000, 1000),
+			max_appeals: 2,
+			appeal_fee: 30000u64.saturated_into::<BalanceOf<T>>(),
+			non_commit_penalty: Percent::from_percent(100),
+			non_reveal_penalty: Percent::from_percent(100),
+			max_commit_redraws: 1,
+			reward_curve: RewardCurve::default(),
 		}
 	}
 
 	pub fn create_with_data(
-		block_length: u64,
+		phase_lengths: PhaseLengths,
 		max_draws: u64,
 		min_number_juror_staked: u64,
 		min_juror_stake: u64,
 		juror_incentives: (u64, u64),
+		max_appeals: u32,
 	) -> Self {
-		let block_length = block_length.saturated_into::<BlockNumberOf<T>>();
+		let to_block = |length: u64| length.saturated_into::<BlockNumberOf<T>>();
+		// Appeal fee covers the stake of every juror of the first round
+		let appeal_fee = min_juror_stake.saturating_mul(max_draws).saturated_into::<BalanceOf<T>>();
 		let min_juror_stake = min_juror_stake.saturated_into::<BalanceOf<T>>();
 		PhaseData {
-			evidence_length: block_length,
-			staking_length: block_length,
-			drawing_length: block_length,
-			commit_length: block_length,
-			vote_length: block_length,
-			appeal_length: block_length,
+			evidence_length: to_block(phase_lengths.evidence),
+			staking_length: to_block(phase_lengths.staking),
+			drawing_length: to_block(phase_lengths.drawing),
+			commit_length: to_block(phase_lengths.commit),
+			vote_length: to_block(phase_lengths.vote),
+			appeal_length: to_block(phase_lengths.appeal),
 			max_draws,
+			// Majority of the jury
+			min_draws: max_draws / 2 + 1,
 			min_number_juror_staked,
 			min_juror_stake,
 			juror_incentives,
+			max_appeals,
+			appeal_fee,
+			non_commit_penalty: Percent::from_percent(100),
+			non_reveal_penalty: Percent::from_percent(100),
+			max_commit_redraws: 1,
+			reward_curve: RewardCurve::default(),
+		}
+	}
+
+	/// Period lengths, `max_draws`, `min_number_juror_staked` and `min_juror_stake` are non zero,
+	/// `min_draws` is non zero and not more than `max_draws`, jurors drawn in the last appeal
+	/// round are not more than `MaxDrawsPerRound`, drawing period is longer than the
+	/// `availability_delay` of `RandomnessSource`
+	/// and `max_distance` of the reward curve is not negative
+	pub fn is_valid(&self) -> bool {
+		let lengths = [
+			self.evidence_length,
+			self.staking_length,
+			self.drawing_length,
+			self.commit_length,
+			self.vote_length,
+			self.appeal_length,
+		];
+		lengths.iter().all(|length| !length.is_zero())
+			&& self.max_draws > 0
+			&& self.min_draws > 0
+			&& self.min_draws <= self.max_draws
+			&& Pallet::<T>::get_max_draws_for_round(self, self.max_appeals)
+				<= T::MaxDrawsPerRound::get() as u64
+			&& self.drawing_length > T::RandomnessSource::availability_delay()
+			&& self.min_number_juror_staked > 0
+			&& !self.min_juror_stake.is_zero()
+			&& self.reward_curve.max_distance >= 0
+	}
+}
+
+impl PhaseLengths {
+	/// Same length for all periods
+	pub fn uniform(block_length: u64) -> Self {
+		PhaseLengths {
+			evidence: block_length,
+			staking: block_length,
+			drawing: block_length,
+			commit: block_length,
+			vote: block_length,
+			appeal: block_length,
+		}
+	}
+}
+
+impl Default for RewardCurve {
+	/// Losers lose one fourth of the stake, winners share the winner incentives equally
+	fn default() -> Self {
+		RewardCurve {
+			loser_slash: Percent::from_percent(25),
+			max_distance: 0,
+			stake_weighted: false,
 		}
 	}
 }
@@ -18,25 +18,38 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 
+mod appeal;
+mod commit;
 mod extras;
+mod finalize;
 mod functions;
+pub mod migrations;
+mod multi_choice;
+pub mod randomness;
 pub mod types;
+mod redraw;
+mod reputation;
+mod scheduler;
 mod score_game;
+mod settlement;
 mod share_link;
 
 use crate::types::{
-	CommitVote, Period, PhaseData, RangePoint, RevealedVote, SchellingGameType, ScoreCommitVote,
-	VoteStatus, WinningDecision,
+	CommitChoice, CommitVote, GameOutcome, GameResult, JurorStats, MultiChoiceCommitVote,
+	NonParticipation, Period, PhaseData, PhaseLengths, RangePoint, RevealedVote, RewardCurve,
+	SchellingGameType, ScoreAggregation, ScoreCommitVote, SettlementProgress, VoteStatus,
+	WinnersIncentives, WinningDecision, WinningOption,
 };
 use frame_support::pallet_prelude::*;
-use frame_support::sp_runtime::traits::{CheckedAdd, CheckedSub};
-use frame_support::sp_runtime::SaturatedConversion;
+use frame_support::sp_runtime::traits::{CheckedAdd, CheckedSub, One, Saturating, Zero};
+use frame_support::sp_runtime::{Percent, SaturatedConversion};
 use frame_support::sp_std::prelude::*;
-use frame_support::traits::Randomness;
-use frame_support::traits::{Currency, OnUnbalanced, ReservableCurrency};
+use frame_support::traits::{
+	Currency, ExistenceRequirement, OnUnbalanced, ReservableCurrency, WithdrawReasons,
+};
 use num_integer::Roots;
-use scale_info::prelude::format;
-use sortition_sum_game::types::SumTreeName;
+use randomness::DrawRandomness;
+use schelling_game_shared_link::OnGameConcluded;
 use sortition_sum_game_link::SortitionSumGameLink;
 
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
@@ -48,8 +61,9 @@ type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
-type SumTreeNameType<T> = SumTreeName<AccountIdOf<T>, BlockNumberOf<T>>;
+type SumTreeNameType<T> = <T as Config>::GameKey;
 type PhaseDataOf<T> = PhaseData<T>;
+type DrawnJurorsOf<T> = BoundedVec<(AccountIdOf<T>, u64), <T as Config>::MaxDrawsPerRound>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -57,8 +71,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 keeps unstaked jurors and jurors who got incentives in double maps
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -69,20 +86,53 @@ pub mod pallet {
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
+		/// Key of a game, it is also the key of the sortition sum tree of the game
+		type GameKey: Parameter + Ord + MaxEncodedLen;
+
 		type SortitionSumGameSource: SortitionSumGameLink<
-			SumTreeName = SumTreeName<Self::AccountId, Self::BlockNumber>,
+			SumTreeName = Self::GameKey,
 			AccountId = Self::AccountId,
 		>;
 
 		type Currency: ReservableCurrency<Self::AccountId>;
 
-		type RandomnessSource: Randomness<Self::Hash, Self::BlockNumber>;
+		/// Randomness used to draw jurors, see `randomness` module for the providers
+		type RandomnessSource: DrawRandomness<Self::Hash, Self::BlockNumber>;
 
 		/// Handler for the unbalanced increment when rewarding (minting rewards)
 		type Reward: OnUnbalanced<PositiveImbalanceOf<Self>>;
 
 		/// Handler for the unbalanced decrement when slashing (burning collateral)
 		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Maximum number of games whose period is changed in `on_initialize` of a block
+		/// Games scheduled for a full block are scheduled for the next block that is not full
+		#[pallet::constant]
+		type MaxPeriodChangesPerBlock: Get<u32>;
+
+		/// Number of blocks the storage of a settled game is kept before it is pruned
+		#[pallet::constant]
+		type GameRetentionPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of settled games pruned in `on_initialize` of a block
+		/// Games settled when the prune block is full are pruned in the next block that is not full
+		#[pallet::constant]
+		type MaxGamesPrunedPerBlock: Get<u32>;
+
+		/// Maximum number of jurors drawn in a round, including the last appeal round
+		/// It also limits the jurors replaced for not committing in a game
+		#[pallet::constant]
+		type MaxDrawsPerRound: Get<u32>;
+
+		/// Minimum length of the salt used in the commit of a vote
+		#[pallet::constant]
+		type MinSaltLength: Get<u32>;
+
+		/// Origin allowed to set the phase data of a schelling game type
+		type PhaseDataOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Called when the outcome of a game is decided
+		type OnGameConcluded: OnGameConcluded<SumTreeNameType<Self>, GameResult<Self::BlockNumber>>;
 	}
 
 	// The pallet's runtime storage items.
@@ -115,6 +165,12 @@ pub mod pallet {
 	pub type StakingStartTime<T> =
 		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, BlockNumberOf<T>, ValueQuery>;
 
+	/// Start of the current drawing period, drawing times out after `drawing_length`
+	#[pallet::storage]
+	#[pallet::getter(fn drawing_start_time)]
+	pub type DrawingStartTime<T> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, BlockNumberOf<T>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn commit_start_time)]
 	pub type CommitStartTime<T> =
@@ -125,17 +181,96 @@ pub mod pallet {
 	pub type VoteStartTime<T> =
 		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, BlockNumberOf<T>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn appeal_start_time)]
+	pub type AppealStartTime<T> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, BlockNumberOf<T>, ValueQuery>;
+
+	/// Current round of the game, `0` for the first round, incremented on every appeal
+	#[pallet::storage]
+	#[pallet::getter(fn appeal_round)]
+	pub type AppealRound<T> = StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, u32, ValueQuery>;
+
+	/// Drawn jurors of the rounds that were appealed: (key, round) => Vec<(AccountId, Stake)>
+	#[pallet::storage]
+	#[pallet::getter(fn past_round_drawn_jurors)]
+	pub type PastRoundDrawnJurors<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Twox64Concat,
+		u32,
+		DrawnJurorsOf<T>,
+		ValueQuery,
+	>;
+
+	/// Block after which randomness can be used to draw jurors, set when drawing period starts
+	#[pallet::storage]
+	#[pallet::getter(fn draw_seed_block)]
+	pub type DrawSeedBlock<T> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, BlockNumberOf<T>, ValueQuery>;
+
+	/// Random seed of the drawing period, fixed by its first draw after `DrawSeedBlock`
+	#[pallet::storage]
+	#[pallet::getter(fn draw_seed)]
+	pub type DrawSeed<T> = StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, [u8; 32]>;
+
+	/// Drawn jurors who did not commit and were replaced: key => Vec<(AccountId, Stake)>
+	#[pallet::storage]
+	#[pallet::getter(fn replaced_jurors)]
+	pub type ReplacedJurors<T: Config> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, DrawnJurorsOf<T>, ValueQuery>;
+
+	/// Number of times jurors without commit were replaced in the current round
+	#[pallet::storage]
+	#[pallet::getter(fn commit_redraws)]
+	pub type CommitRedraws<T> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, u32, ValueQuery>;
+
+	/// Games whose period deadline expires at a block: BlockNumber => Vec<SumTreeName>
+	/// Period of these games is changed in `on_initialize`
+	#[pallet::storage]
+	#[pallet::getter(fn period_change_queue)]
+	pub type PeriodChangeQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberOf<T>,
+		BoundedVec<SumTreeNameType<T>, T::MaxPeriodChangesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Phase data of the game, stored when the game starts and kept until the game is pruned
+	/// Used for the period changes in `PeriodChangeQueue` and read by consumer pallets, so a game
+	/// is not affected by later changes of `GameTypePhaseData`
+	#[pallet::storage]
+	#[pallet::getter(fn game_phase_data)]
+	pub type GamePhaseData<T: Config> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, PhaseDataOf<T>>;
+
+	/// Phase data of a schelling game type, set by `PhaseDataOrigin`
+	/// Pallets use their own phase data for game types without one
+	#[pallet::storage]
+	#[pallet::getter(fn game_type_phase_data)]
+	pub type GameTypePhaseData<T: Config> =
+		StorageMap<_, Blake2_128Concat, SchellingGameType, PhaseDataOf<T>>;
+
 	/// Drawn jurors containing account id and stake Vec<(AccountId, Stake)>
 	/// Should be stored in sorted order by AccountId
 	#[pallet::storage]
 	#[pallet::getter(fn  drawn_jurors)]
 	pub type DrawnJurors<T: Config> =
-		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, Vec<(T::AccountId, u64)>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, DrawnJurorsOf<T>, ValueQuery>;
 
+	/// Jurors who unstaked from the game: (key, AccountId) => ()
 	#[pallet::storage]
-	#[pallet::getter(fn unstaked_jurors)]
-	pub type UnstakedJurors<T: Config> =
-		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, Vec<T::AccountId>, ValueQuery>;
+	pub type UnstakedJurors<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+	>;
 
 	/// VoteCommits for Yes or No voting
 	#[pallet::storage]
@@ -161,11 +296,58 @@ pub mod pallet {
 		ScoreCommitVote,
 	>;
 
+	/// Vote Commits for Multi Choice Schelling
+	#[pallet::storage]
+	#[pallet::getter(fn vote_commits_multi_choice)]
+	pub type MultiChoiceVoteCommits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		MultiChoiceCommitVote,
+	>;
+
+	/// Count of revealed votes for each option of multi choice schelling game: (key, option) => count
+	#[pallet::storage]
+	#[pallet::getter(fn multi_choice_count)]
+	pub type MultiChoiceCount<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Twox64Concat,
+		u32,
+		u64,
+		ValueQuery,
+	>;
+
+	/// Number of options of multi choice schelling game, set when the game is created
+	#[pallet::storage]
+	#[pallet::getter(fn number_of_options)]
+	pub type MultiChoiceOptions<T: Config> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, u32>;
+
 	/// Reveal values of score schelling game as Vec<i64>
 	#[pallet::storage]
 	#[pallet::getter(fn reveal_score_values)]
-	pub type RevealScoreValues<T: Config> =
-		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, Vec<i64>, ValueQuery>;
+	pub type RevealScoreValues<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		BoundedVec<i64, T::MaxDrawsPerRound>,
+		ValueQuery,
+	>;
+
+	/// Stakes of the jurors of `RevealScoreValues`, in the same order
+	#[pallet::storage]
+	#[pallet::getter(fn reveal_score_stakes)]
+	pub type RevealScoreStakes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		BoundedVec<u64, T::MaxDrawsPerRound>,
+		ValueQuery,
+	>;
 
 	/// New mean from the reveal values in score schelling game
 	/// Improvement: This step will not be required if all jurors incentives are distributed at one time
@@ -174,16 +356,51 @@ pub mod pallet {
 	pub type IncentiveMeanRevealScore<T: Config> =
 		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, i64, ValueQuery>;
 
+	/// Progress of the paginated settlement of the game, removed once all jurors are settled
+	#[pallet::storage]
+	#[pallet::getter(fn game_settlement)]
+	pub type GameSettlement<T: Config> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, SettlementProgress, ValueQuery>;
+
 	/// Decision count for two choices after reveal vote:  (count for 0, count for 1)
 	#[pallet::storage]
 	#[pallet::getter(fn decision_count)]
 	pub type DecisionCount<T> =
 		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, (u64, u64), ValueQuery>; // Count for 0, Count for 1
 
+	/// Jurors who got their incentives: (key, AccountId) => ()
 	#[pallet::storage]
-	#[pallet::getter(fn juror_incentive_distribution)]
-	pub type JurorsIncentiveDistributedAccounts<T: Config> =
-		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, Vec<T::AccountId>, ValueQuery>;
+	pub type JurorsIncentiveDistributedAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+	>;
+
+	/// Settlement history of the juror, updated when the juror is settled
+	#[pallet::storage]
+	#[pallet::getter(fn juror_stats)]
+	pub type JurorStatistics<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, JurorStats, ValueQuery>;
+
+	/// Result of the settled game, kept after the game storage is pruned
+	#[pallet::storage]
+	#[pallet::getter(fn game_result)]
+	pub type GameResults<T: Config> =
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, GameResult<BlockNumberOf<T>>>;
+
+	/// Settled games whose storage is pruned at a block: BlockNumber => Vec<SumTreeName>
+	#[pallet::storage]
+	#[pallet::getter(fn prune_queue)]
+	pub type PruneQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberOf<T>,
+		BoundedVec<SumTreeNameType<T>, T::MaxGamesPrunedPerBlock>,
+		ValueQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -193,6 +410,67 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		SomethingStored { something: u32, who: T::AccountId },
+		/// Period of the game changed. [key, period]
+		PeriodChanged { key: SumTreeNameType<T>, period: Period },
+		/// Juror applied with stake. [key, who, stake]
+		JurorStaked { key: SumTreeNameType<T>, who: T::AccountId, stake: BalanceOf<T> },
+		/// Juror drawn for the current round. [key, who, stake]
+		JurorDrawn { key: SumTreeNameType<T>, who: T::AccountId, stake: u64 },
+		/// Juror who was not drawn got back the stake. [key, who, stake]
+		JurorUnstaked { key: SumTreeNameType<T>, who: T::AccountId, stake: BalanceOf<T> },
+		/// Juror committed the vote. [key, who]
+		VoteCommitted { key: SumTreeNameType<T>, who: T::AccountId },
+		/// Drawn juror did not commit and is replaced by a new draw. [key, who]
+		JurorReplaced { key: SumTreeNameType<T>, who: T::AccountId },
+		/// Juror revealed the vote of two choice game. [key, who, choice]
+		VoteRevealed { key: SumTreeNameType<T>, who: T::AccountId, choice: u128 },
+		/// Juror revealed the vote of score game. [key, who, score]
+		ScoreVoteRevealed { key: SumTreeNameType<T>, who: T::AccountId, score: i64 },
+		/// Ruling of the round was appealed, new round started. [key, who, round]
+		Appealed { key: SumTreeNameType<T>, who: T::AccountId, round: u32 },
+		/// Two choice game decided. [key, decision]
+		GameDecided { key: SumTreeNameType<T>, decision: WinningDecision },
+		/// Juror revealed the vote of multi choice game. [key, who, choice]
+		MultiChoiceVoteRevealed { key: SumTreeNameType<T>, who: T::AccountId, choice: u32 },
+		/// Multi choice game decided. [key, decision]
+		MultiChoiceGameDecided { key: SumTreeNameType<T>, decision: WinningOption },
+		/// Score game decided, score is multiplied by 1000. [key, score]
+		ScoreGameDecided { key: SumTreeNameType<T>, score: i64 },
+		/// Juror got stake back with incentives. [key, who, amount]
+		IncentivesPaid { key: SumTreeNameType<T>, who: T::AccountId, amount: BalanceOf<T> },
+		/// Juror stake slashed for voting against the decision. [key, who, amount]
+		JurorSlashed { key: SumTreeNameType<T>, who: T::AccountId, amount: BalanceOf<T> },
+		/// Drawn juror stake slashed for not committing or not revealing the vote.
+		/// [key, who, reason, amount]
+		NonParticipationPenalty {
+			key: SumTreeNameType<T>,
+			who: T::AccountId,
+			reason: NonParticipation,
+			amount: BalanceOf<T>,
+		},
+		/// All drawn jurors of the game got their incentives or were slashed. [key]
+		GameSettled { key: SumTreeNameType<T> },
+		/// Game cancelled and stakes of jurors refunded. [key]
+		GameCancelled { key: SumTreeNameType<T> },
+		/// Phase data of the schelling game type set. [game_type]
+		PhaseDataSet { game_type: SchellingGameType },
+		/// `OnGameConcluded` failed, its changes are reverted and the game is settled. [key, error]
+		GameConcludedHookFailed { key: SumTreeNameType<T>, error: DispatchError },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Change the period of the games whose period deadline expires at `now`
+		/// and prune the storage of settled games whose retention period ends at `now`
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::advance_periods(now).saturating_add(Self::prune_games(now))
+		}
+
+		fn integrity_test() {
+			assert!(T::MaxPeriodChangesPerBlock::get() > 0, "MaxPeriodChangesPerBlock is zero");
+			assert!(T::MaxGamesPrunedPerBlock::get() > 0, "MaxGamesPrunedPerBlock is zero");
+			assert!(T::MaxDrawsPerRound::get() > 0, "MaxDrawsPerRound is zero");
+		}
 	}
 
 	// Errors inform users that something went wrong.
@@ -225,6 +503,26 @@ pub mod pallet {
 		CommitDoesNotExists,
 		AlreadyGotIncentives,
 		VoteNotRevealed,
+		AppealPeriodNotOver,
+		AppealPeriodOver,
+		MaxAppealsReached,
+		DrawnInPreviousRound,
+		RandomnessNotAvailable,
+		NoScoreRevealed,
+		GameAlreadySettled,
+		SaltTooShort,
+		InvalidPhaseData,
+		GameAlreadyConcluded,
+		/// Drawn jurors or revealed scores of the round are more than `MaxDrawsPerRound`
+		TooManyJurors,
+		/// The choice of the appellant won the round
+		NotLosingParty,
+		/// Multi choice game needs at least two options
+		InvalidNumberOfOptions,
+		NumberOfOptionsAlreadySet,
+		NumberOfOptionsNotSet,
+		/// Stakes are refunded with `refund_stakes` only for settled or cancelled games
+		GameNotSettled,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -270,5 +568,51 @@ pub mod pallet {
 				},
 			}
 		}
+
+		/// Set the phase data used by new games of `game_type`
+		/// Games already started keep their phase data
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_phase_data())]
+		pub fn set_phase_data(
+			origin: OriginFor<T>,
+			game_type: SchellingGameType,
+			phase_data: PhaseDataOf<T>,
+		) -> DispatchResult {
+			T::PhaseDataOrigin::ensure_origin(origin)?;
+			ensure!(phase_data.is_valid(), Error::<T>::InvalidPhaseData);
+
+			<GameTypePhaseData<T>>::insert(&game_type, phase_data);
+			Self::deposit_event(Event::PhaseDataSet { game_type });
+			Ok(())
+		}
+
+		/// Cancel a game that is not decided yet
+		/// Stakes of the drawn jurors and of up to `MaxDrawsPerRound` other stakers are refunded,
+		/// the result is recorded as `GameOutcome::Cancelled`
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_game(Pallet::<T>::max_cancel_refunds()))]
+		pub fn cancel_game(
+			origin: OriginFor<T>,
+			key: SumTreeNameType<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let refunded = Self::cancel_game_helper(key)?;
+			Ok(Some(T::WeightInfo::cancel_game(refunded)).into())
+		}
+
+		/// Refund at most `limit` stakers left in the sortition sum tree of a settled or cancelled
+		/// game
+		/// The tree is removed once all stakers are refunded
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::refund_stakes(*limit))]
+		pub fn refund_stakes(
+			origin: OriginFor<T>,
+			key: SumTreeNameType<T>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let _who = ensure_signed(origin)?;
+			let refunded = Self::refund_stakes_helper(key, limit)?;
+			Ok(Some(T::WeightInfo::refund_stakes(refunded)).into())
+		}
 	}
 }
@@ -0,0 +1,59 @@
+//! Storage migrations of the schelling game shared pallet
+
+use crate::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+pub mod v1 {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub(crate) type UnstakedJurors<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, SumTreeNameType<T>, Vec<AccountIdOf<T>>>;
+
+	#[frame_support::storage_alias]
+	pub(crate) type JurorsIncentiveDistributedAccounts<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, SumTreeNameType<T>, Vec<AccountIdOf<T>>>;
+
+	/// Move unstaked jurors and jurors who got incentives into double maps
+	/// Drawn jurors and revealed scores keep their encoding, `MaxDrawsPerRound` must not be
+	/// less than the jurors drawn in a round of the games in progress
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+
+			let unstaked: Vec<_> = UnstakedJurors::<T>::drain().collect();
+			for (key, jurors) in unstaked.iter() {
+				for juror in jurors.iter() {
+					<crate::UnstakedJurors<T>>::insert(key, juror, ());
+				}
+				reads += 1;
+				writes += 1 + jurors.len() as u64;
+			}
+
+			let incentives: Vec<_> = JurorsIncentiveDistributedAccounts::<T>::drain().collect();
+			for (key, jurors) in incentives.iter() {
+				for juror in jurors.iter() {
+					<crate::JurorsIncentiveDistributedAccounts<T>>::insert(key, juror, ());
+				}
+				reads += 1;
+				writes += 1 + jurors.len() as u64;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
@@ -1,5 +1,11 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+	dispatch::DispatchResult,
+	storage::unhashed,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use schelling_game_shared_link::OnGameConcluded;
+use sortition_sum_game::types::SumTreeName;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -8,6 +14,7 @@ use sp_runtime::{
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+use crate::randomness::InstantRandomness;
 use frame_support_test::TestRandomness;
 
 // Configure a mock runtime to test the pallet.
@@ -54,16 +61,46 @@ impl frame_system::Config for Test {
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = SumTreeName<u64, u64>;
 	type Currency = Balances; // New code
-	type RandomnessSource = TestRandomness<Self>;
+	type RandomnessSource = InstantRandomness<TestRandomness<Self>>;
 	type Slash = ();
 	type Reward = ();
+	type MaxPeriodChangesPerBlock = ConstU32<50>;
+	type GameRetentionPeriod = ConstU64<100>;
+	type MaxGamesPrunedPerBlock = ConstU32<50>;
+	type MaxDrawsPerRound = ConstU32<50>;
+	type MinSaltLength = ConstU32<4>;
+	type PhaseDataOrigin = frame_system::EnsureRoot<u64>;
+	type OnGameConcluded = TestGameConcluded;
 	type SortitionSumGameSource = SortitionSumGame;
 }
 
+pub const CONCLUDED_KEY: &[u8] = b"concluded";
+pub const FAILING_CITIZEN: u64 = 99;
+
+/// Records the last concluded game, fails after the write for `FAILING_CITIZEN`
+pub struct TestGameConcluded;
+
+impl OnGameConcluded<SumTreeName<u64, u64>, crate::types::GameResult<u64>> for TestGameConcluded {
+	fn on_game_concluded(
+		key: SumTreeName<u64, u64>,
+		_result: crate::types::GameResult<u64>,
+	) -> DispatchResult {
+		unhashed::put(CONCLUDED_KEY, &key);
+		match key {
+			SumTreeName::ProfileValidation { citizen_address: FAILING_CITIZEN, .. } => {
+				Err("hook failed".into())
+			},
+			_ => Ok(()),
+		}
+	}
+}
+
 impl sortition_sum_game::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = SumTreeName<u64, u64>;
 }
 
 impl pallet_balances::Config for Test {
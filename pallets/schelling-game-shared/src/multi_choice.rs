@@ -0,0 +1,131 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Set the number of options of a multi choice game before jurors commit
+	/// Revealed choices are checked against it, it can not be changed once set
+	pub(super) fn set_number_of_options_helper(
+		key: SumTreeNameType<T>,
+		number_of_options: u32,
+	) -> DispatchResult {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(
+					period == Period::Evidence || period == Period::Staking,
+					Error::<T>::PeriodDontMatch
+				);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		ensure!(number_of_options >= 2, Error::<T>::InvalidNumberOfOptions);
+		ensure!(
+			!<MultiChoiceOptions<T>>::contains_key(&key),
+			Error::<T>::NumberOfOptionsAlreadySet
+		);
+		<MultiChoiceOptions<T>>::insert(&key, number_of_options);
+		Ok(())
+	}
+
+	/// Commit your multi choice vote
+	pub(super) fn commit_vote_multi_choice_helper(
+		key: SumTreeNameType<T>,
+		who: AccountIdOf<T>,
+		vote_commit: [u8; 32],
+	) -> DispatchResult {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Commit, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		let drawn_jurors = <DrawnJurors<T>>::get(&key);
+		match drawn_jurors.binary_search_by(|(c, _)| c.cmp(&who.clone())) {
+			Ok(_) => {
+				let vote_commit_struct = MultiChoiceCommitVote {
+					commit: vote_commit,
+					votestatus: VoteStatus::Commited,
+					revealed_vote: None,
+				};
+				<MultiChoiceVoteCommits<T>>::insert(&key, &who, vote_commit_struct);
+				Self::deposit_event(Event::VoteCommitted { key: key.clone(), who: who.clone() });
+			},
+			Err(_) => Err(Error::<T>::JurorDoesNotExists)?,
+		}
+		Ok(())
+	}
+
+	/// Reveal the multi choice vote
+	/// `choice` is the index of the option, it must be less than the number of options of the game
+	pub(super) fn reveal_vote_multi_choice_helper(
+		key: SumTreeNameType<T>,
+		who: AccountIdOf<T>,
+		choice: u32,
+		salt: Vec<u8>,
+	) -> DispatchResult {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Vote, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		let number_of_options =
+			<MultiChoiceOptions<T>>::get(&key).ok_or(Error::<T>::NumberOfOptionsNotSet)?;
+		ensure!(choice < number_of_options, Error::<T>::NotValidChoice);
+		let who_commit_vote = <MultiChoiceVoteCommits<T>>::get(&key, &who);
+		match who_commit_vote {
+			Some(mut commit_struct) => {
+				ensure!(
+					commit_struct.votestatus == VoteStatus::Commited,
+					Error::<T>::VoteStatusNotCommited
+				);
+				// Commits of appealed rounds can not be revealed in the current round
+				ensure!(
+					Self::selected_as_juror_helper(key.clone(), who.clone()),
+					Error::<T>::JurorDoesNotExists
+				);
+				Self::ensure_commit_matches(
+					&key,
+					&who,
+					&CommitChoice::MultiChoice(choice),
+					&salt,
+					&commit_struct.commit,
+				)?;
+				<MultiChoiceCount<T>>::mutate(&key, choice, |count| *count += 1);
+				commit_struct.revealed_vote = Some(choice);
+				commit_struct.votestatus = VoteStatus::Revealed;
+				<MultiChoiceVoteCommits<T>>::insert(&key, &who, commit_struct);
+				Self::deposit_event(Event::MultiChoiceVoteRevealed {
+					key: key.clone(),
+					who: who.clone(),
+					choice,
+				});
+			},
+			None => Err(Error::<T>::CommitDoesNotExists)?,
+		}
+
+		Ok(())
+	}
+
+	/// Option with the highest count of votes in the current round
+	/// Returns `WinningOption::Draw` if the highest count is shared by more than one option
+	pub(super) fn get_winning_option(key: SumTreeNameType<T>) -> WinningOption {
+		let mut winning_option = WinningOption::Draw;
+		let mut highest_count = 0;
+		for (option, count) in <MultiChoiceCount<T>>::iter_prefix(&key) {
+			if count > highest_count {
+				highest_count = count;
+				winning_option = WinningOption::Winner(option);
+			} else if count == highest_count {
+				winning_option = WinningOption::Draw;
+			}
+		}
+		winning_option
+	}
+
+	/// Winning option of the game, `None` in case of draw
+	pub(super) fn get_winning_option_helper(key: SumTreeNameType<T>) -> Option<u32> {
+		match Self::get_winning_option(key) {
+			WinningOption::Winner(option) => Some(option),
+			WinningOption::Draw => None,
+		}
+	}
+}
@@ -0,0 +1,112 @@
+//! Randomness providers used to draw jurors
+//!
+//! The seed block is fixed at the `Period::Staking` to `Period::Drawing` transition,
+//! and jurors can only be drawn with randomness that was determined after the seed block,
+//! so the randomness is unknown when the stakes are placed.
+//! The first draw of the drawing period stores the seed, later draws of the period derive
+//! their seed from it, so it does not change with the block the jurors are drawn in.
+use frame_support::sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero};
+use frame_support::sp_std::marker::PhantomData;
+use frame_support::traits::{Get, Randomness};
+
+/// Provider of the random seed for drawing jurors
+pub trait DrawRandomness<Output, BlockNumber> {
+	/// Block number after which randomness for the draw can be used,
+	/// called when drawing period starts at `now`
+	fn seed_block(now: BlockNumber) -> BlockNumber;
+
+	/// Random value for `subject`, `None` if randomness determined after `seed_block`
+	/// is not yet available
+	fn random(subject: &[u8], seed_block: BlockNumber) -> Option<Output>;
+
+	/// Blocks after the start of drawing period before `random` returns a value
+	/// The drawing period of `PhaseData` must be longer
+	fn availability_delay() -> BlockNumber;
+}
+
+/// Randomness of a block at least `Delay` blocks after the start of drawing period
+/// `R` returns the block number since which its randomness is determined,
+/// randomness is only used if it was determined after the seed block
+/// `Lag` is how many blocks the block number returned by `R` trails the current block,
+/// 81 for `pallet_insecure_randomness_collective_flip`
+pub struct FutureBlockRandomness<R, Delay, Lag = ()>(PhantomData<(R, Delay, Lag)>);
+
+impl<Output, BlockNumber, R, Delay, Lag> DrawRandomness<Output, BlockNumber>
+	for FutureBlockRandomness<R, Delay, Lag>
+where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+	R: Randomness<Output, BlockNumber>,
+	Delay: Get<BlockNumber>,
+	Lag: Get<BlockNumber>,
+{
+	fn seed_block(now: BlockNumber) -> BlockNumber {
+		now.saturating_add(Delay::get())
+	}
+
+	fn availability_delay() -> BlockNumber {
+		Delay::get().saturating_add(Lag::get())
+	}
+
+	fn random(subject: &[u8], seed_block: BlockNumber) -> Option<Output> {
+		let (output, known_since) = R::random(subject);
+		if known_since >= seed_block {
+			Some(output)
+		} else {
+			None
+		}
+	}
+}
+
+/// Adapter for VRF based randomness such as `pallet_babe::ParentBlockRandomness`,
+/// whose output is `None` when no VRF output is available for the block
+/// `Lag` is how many blocks the block number returned by `R` trails the current block,
+/// 1 for `pallet_babe::ParentBlockRandomness`
+pub struct VrfRandomness<R, Delay, Lag = ()>(PhantomData<(R, Delay, Lag)>);
+
+impl<Output, BlockNumber, R, Delay, Lag> DrawRandomness<Output, BlockNumber>
+	for VrfRandomness<R, Delay, Lag>
+where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+	R: Randomness<Option<Output>, BlockNumber>,
+	Delay: Get<BlockNumber>,
+	Lag: Get<BlockNumber>,
+{
+	fn seed_block(now: BlockNumber) -> BlockNumber {
+		now.saturating_add(Delay::get())
+	}
+
+	fn availability_delay() -> BlockNumber {
+		Delay::get().saturating_add(Lag::get())
+	}
+
+	fn random(subject: &[u8], seed_block: BlockNumber) -> Option<Output> {
+		let (output, known_since) = R::random(subject);
+		if known_since >= seed_block {
+			output
+		} else {
+			None
+		}
+	}
+}
+
+/// Randomness of `R` used as it is, the seed can be known when stakes are placed
+/// Only for tests and development chains
+pub struct InstantRandomness<R>(PhantomData<R>);
+
+impl<Output, BlockNumber, R> DrawRandomness<Output, BlockNumber> for InstantRandomness<R>
+where
+	BlockNumber: Zero,
+	R: Randomness<Output, BlockNumber>,
+{
+	fn seed_block(now: BlockNumber) -> BlockNumber {
+		now
+	}
+
+	fn random(subject: &[u8], _seed_block: BlockNumber) -> Option<Output> {
+		Some(R::random(subject).0)
+	}
+
+	fn availability_delay() -> BlockNumber {
+		Zero::zero()
+	}
+}
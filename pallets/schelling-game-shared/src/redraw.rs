@@ -0,0 +1,37 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Juror has a commit in two choice, score or multi choice schelling game
+	pub(super) fn has_committed(key: SumTreeNameType<T>, who: AccountIdOf<T>) -> bool {
+		<VoteCommits<T>>::contains_key(&key, &who)
+			|| <ScoreVoteCommits<T>>::contains_key(&key, &who)
+			|| <MultiChoiceVoteCommits<T>>::contains_key(&key, &who)
+	}
+
+	/// Move drawn jurors who did not commit to `ReplacedJurors`,
+	/// so that replacements can be drawn from the sortition tree
+	/// Their stake stays reserved, and non participation penalty is applied in execution
+	/// Returns `false` if all drawn jurors have committed, or if `ReplacedJurors` can not hold
+	/// the jurors without commit
+	pub(super) fn replace_jurors_without_commit(key: SumTreeNameType<T>) -> bool {
+		let (committed, not_committed): (Vec<_>, Vec<_>) = <DrawnJurors<T>>::get(&key)
+			.into_iter()
+			.partition(|(juror, _)| Self::has_committed(key.clone(), juror.clone()));
+		if not_committed.is_empty() {
+			return false;
+		}
+		let mut replaced_jurors = <ReplacedJurors<T>>::get(&key);
+		if replaced_jurors.len() + not_committed.len() > T::MaxDrawsPerRound::get() as usize {
+			return false;
+		}
+		<DrawsInRound<T>>::insert(&key, committed.len() as u64);
+		// Committed jurors are part of the drawn jurors, so they are within the bound
+		<DrawnJurors<T>>::insert(&key, DrawnJurorsOf::<T>::truncate_from(committed));
+		for (juror, stake) in not_committed {
+			Self::deposit_event(Event::JurorReplaced { key: key.clone(), who: juror.clone() });
+			let _ = replaced_jurors.try_push((juror, stake));
+		}
+		<ReplacedJurors<T>>::insert(&key, replaced_jurors);
+		true
+	}
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Update `JurorStatistics` of a settled juror
+	/// `non_participation` is `None` if the juror revealed the vote
+	pub(super) fn record_juror_settlement(
+		who: &AccountIdOf<T>,
+		non_participation: Option<NonParticipation>,
+		coherent: bool,
+		slashed: u64,
+		rewards: u64,
+	) {
+		<JurorStatistics<T>>::mutate(who, |stats| {
+			stats.games_drawn = stats.games_drawn.saturating_add(1);
+			match non_participation {
+				Some(NonParticipation::NotCommitted) => {},
+				Some(NonParticipation::NotRevealed) => {
+					stats.committed = stats.committed.saturating_add(1);
+				},
+				None => {
+					stats.committed = stats.committed.saturating_add(1);
+					stats.revealed = stats.revealed.saturating_add(1);
+				},
+			}
+			if coherent {
+				stats.coherent = stats.coherent.saturating_add(1);
+			}
+			stats.slashed = stats.slashed.saturating_add(slashed);
+			stats.rewards = stats.rewards.saturating_add(rewards);
+		});
+	}
+
+	/// Part of the revealed votes of the juror that agreed with the decision
+	/// Zero if the juror has not revealed any vote
+	pub fn juror_coherence(who: &AccountIdOf<T>) -> Percent {
+		let stats = <JurorStatistics<T>>::get(who);
+		if stats.revealed == 0 {
+			Percent::from_percent(0)
+		} else {
+			Percent::from_rational(stats.coherent, stats.revealed)
+		}
+	}
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Schedule the period change of the game at block `at`
+	/// If `at` is not in the future, period change is scheduled for the next block
+	/// If `MaxPeriodChangesPerBlock` games are scheduled at `at`, the first block after `at`
+	/// that is not full is used
+	pub(super) fn schedule_period_change(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		at: BlockNumberOf<T>,
+	) {
+		let next_block = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+		let mut at = if at < next_block { next_block } else { at };
+		<GamePhaseData<T>>::insert(&key, phase_data);
+		while <PeriodChangeQueue<T>>::try_append(at, &key).is_err() {
+			at = at.saturating_add(One::one());
+		}
+	}
+
+	/// Change the period of games scheduled at block `now`
+	/// Period change errors are ignored, as `change_period` checks the period is over
+	/// Games cancelled by the period change are charged with the refunds of the cancellation
+	pub(super) fn advance_periods(now: BlockNumberOf<T>) -> Weight {
+		let keys = <PeriodChangeQueue<T>>::take(now);
+		let mut weight = T::WeightInfo::advance_periods(keys.len() as u32);
+		for key in keys {
+			if let Some(phase_data) = <GamePhaseData<T>>::get(&key) {
+				let concluded = <GameResults<T>>::contains_key(&key);
+				let result = Self::change_period(key.clone(), phase_data, now);
+				if result.is_ok() && !concluded && <GameResults<T>>::contains_key(&key) {
+					weight = weight
+						.saturating_add(T::WeightInfo::cancel_game(Self::max_cancel_refunds()));
+				}
+			}
+		}
+		weight
+	}
+}
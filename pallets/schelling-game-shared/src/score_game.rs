@@ -23,6 +23,7 @@ impl<T: Config> Pallet<T> {
 					revealed_vote: None,
 				};
 				<ScoreVoteCommits<T>>::insert(&key, &who, vote_commit_struct);
+				Self::deposit_event(Event::VoteCommitted { key: key.clone(), who: who.clone() });
 			},
 			Err(_) => Err(Error::<T>::JurorDoesNotExists)?,
 		}
@@ -49,23 +50,36 @@ impl<T: Config> Pallet<T> {
 					commit_struct.votestatus == VoteStatus::Commited,
 					Error::<T>::VoteStatusNotCommited
 				);
-				let mut vote = format!("{}", choice).as_bytes().to_vec();
-				// let mut vote = choice.clone();
-				let mut salt_a = salt.clone();
-				vote.append(&mut salt_a);
-				let vote_bytes: &[u8] = &vote;
-				let hash = sp_io::hashing::keccak_256(vote_bytes);
-				let commit: &[u8] = &commit_struct.commit;
-				if hash == commit {
-					let mut reveal_score_values = <RevealScoreValues<T>>::get(&key);
-					reveal_score_values.push(choice * 1000);
-					<RevealScoreValues<T>>::insert(&key, reveal_score_values);
-					commit_struct.revealed_vote = Some(choice);
-					commit_struct.votestatus = VoteStatus::Revealed;
-					<ScoreVoteCommits<T>>::insert(&key, &who, commit_struct);
-				} else {
-					Err(Error::<T>::CommitDoesNotMatch)?
-				}
+				// Commits of appealed rounds can not be revealed in the current round
+				ensure!(
+					Self::selected_as_juror_helper(key.clone(), who.clone()),
+					Error::<T>::JurorDoesNotExists
+				);
+				Self::ensure_commit_matches(
+					&key,
+					&who,
+					&CommitChoice::Score(choice),
+					&salt,
+					&commit_struct.commit,
+				)?;
+				let drawn_jurors = <DrawnJurors<T>>::get(&key);
+				let stake = drawn_jurors
+					.binary_search_by(|(c, _)| c.cmp(&who))
+					.map(|index| drawn_jurors[index].1)
+					.unwrap_or(0);
+				let scaled_choice = choice.checked_mul(1000).ok_or(Error::<T>::NotValidChoice)?;
+				<RevealScoreValues<T>>::try_append(&key, scaled_choice)
+					.map_err(|_| Error::<T>::TooManyJurors)?;
+				<RevealScoreStakes<T>>::try_append(&key, stake)
+					.map_err(|_| Error::<T>::TooManyJurors)?;
+				commit_struct.revealed_vote = Some(choice);
+				commit_struct.votestatus = VoteStatus::Revealed;
+				<ScoreVoteCommits<T>>::insert(&key, &who, commit_struct);
+				Self::deposit_event(Event::ScoreVoteRevealed {
+					key: key.clone(),
+					who: who.clone(),
+					score: choice,
+				});
 			},
 			None => Err(Error::<T>::CommitDoesNotExists)?,
 		}
@@ -73,104 +87,40 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Distribute incentives to all jurors in execution period in score schelling game
-	pub(super) fn get_incentives_score_schelling_helper(
-		key: SumTreeNameType<T>,
-		phase_data: PhaseDataOf<T>,
-		range_point: RangePoint,
-	) -> DispatchResult {
-		match <PeriodName<T>>::get(&key) {
-			Some(period) => {
-				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
-			},
-			None => Err(Error::<T>::PeriodDoesNotExists)?,
+	/// Mean of the revealed score multiplied by 1000
+	/// Read from `GameResults`, which is kept after the game storage is pruned
+	pub(super) fn get_mean_value(key: SumTreeNameType<T>) -> i64 {
+		match <GameResults<T>>::get(&key).and_then(|result| result.aggregated_score) {
+			Some(score) => score,
+			None => <IncentiveMeanRevealScore<T>>::get(key.clone()),
 		}
+	}
 
-		let drawn_jurors = <DrawnJurors<T>>::get(&key);
-		let reveal_votes_iterator = <ScoreVoteCommits<T>>::iter_prefix(&key);
-		let reveal_values = <RevealScoreValues<T>>::get(&key);
-		let sd_and_mean = Self::std_deviation_interger(&reveal_values);
-		let new_mean = Self::calculate_new_mean(&reveal_values, sd_and_mean).unwrap();
-		// println!("new mean: {:?}", new_mean);
-		<IncentiveMeanRevealScore<T>>::insert(key.clone(), new_mean);
-		let incentives_range = Self::get_incentives_range(range_point);
-		let mut reveal_votes = reveal_votes_iterator
-			.map(|(account_id, score_commit_vote)| (account_id, score_commit_vote.revealed_vote))
-			.collect::<Vec<(_, _)>>();
-		reveal_votes.sort_by(|a, b| a.0.cmp(&b.0));
-
-		
-		// println!("reveal votes, {:?}",reveal_votes);
-		let mut winners = vec![];
-		for juror in drawn_jurors {
-			match reveal_votes.binary_search_by(|(c, _)| c.cmp(&juror.0)) {
-				Ok(index) => {
-					// println!("Ok index {:?}", index);
-					let account_n_vote = &reveal_votes[index];
-					if let Some(i) = account_n_vote.1 {
-						// println!("vote {:?}", i);
-						if i*1000 >= new_mean.checked_sub(incentives_range).unwrap()
-							&&  i*1000  <= new_mean.checked_add(incentives_range).unwrap()
-						{
-							// get incentives
-							winners.push((juror.0.clone(), juror.1.clone()));
-						} else {
-							// deduct incentives
-							let stake = juror.1;
-							let balance = Self::u64_to_balance_saturated(stake * 3 / 4);
-							let r =
-								T::Currency::deposit_into_existing(&juror.0, balance).ok().unwrap();
-							T::Reward::on_unbalanced(r);
-						}
-					}
-				},
-				Err(_) => {
-					// println!("Err index {:?}", index);
-				},
-			}
-		}
+	/// Revealed scores (multiplied by 1000) with the stake of the juror in the current round
+	pub(super) fn get_revealed_scores(key: SumTreeNameType<T>) -> Vec<(i64, u64)> {
+		<RevealScoreValues<T>>::get(&key)
+			.into_iter()
+			.zip(<RevealScoreStakes<T>>::get(&key))
+			.collect()
+	}
 
-		let winners_len = winners.len() as u64;
-		// println!("winners_len {}", winners_len);
-		let incentives_tuple = phase_data.juror_incentives;
-		let winning_incentives = incentives_tuple.1.checked_div(winners_len).expect("oveflow");
-		for winner in winners {
-			let total_incentives = winner.1.checked_add(winning_incentives).expect("overflow");
-			let incentives = Self::u64_to_balance_saturated(total_incentives);
-			let r = T::Currency::deposit_into_existing(&winner.0, incentives).ok().unwrap();
-			T::Reward::on_unbalanced(r);
+	/// Aggregate the scores into the final score, `None` if no score is revealed
+	pub(super) fn aggregate_score(
+		score_aggregation: &ScoreAggregation,
+		scores: &Vec<(i64, u64)>,
+	) -> Option<i64> {
+		let values = scores.iter().map(|(score, _)| *score).collect::<Vec<i64>>();
+		match score_aggregation {
+			ScoreAggregation::MeanWithinStdDev => {
+				let sd_and_mean = Self::std_deviation_interger(&values)?;
+				Self::calculate_new_mean(&values, Some(sd_and_mean))
+			},
+			ScoreAggregation::Median => Self::median_integer(&values),
+			ScoreAggregation::InterquartileMean => Self::interquartile_mean_integer(&values),
+			ScoreAggregation::StakeWeightedMean => Self::stake_weighted_mean_integer(scores),
 		}
-
-		// Remove all data
-
-		// Remove SorititionSumTrees in `sortition-sum-game` pallet
-		let _result = T::SortitionSumGameSource::remove_tree_link(key.clone());
-
-		// Remove DrawnJurors
-		<DrawnJurors<T>>::remove(&key);
-
-		// Remove UnstakedJurors (all jurors can be returned their incentives at a time)
-	
-
-		// Remove ScoreVoteCommits
-		<ScoreVoteCommits<T>>::remove_prefix(key.clone(), None); // Deprecated: Use clear_prefix instead
-		// let reveal_votes_iterator2 = <ScoreVoteCommits<T>>::iter_prefix(&key);
-		// reveal_votes_iterator2.for_each(|(account_id, _)|{
-		// 	<ScoreVoteCommits<T>>::remove(key.clone(), account_id);
-		// });
-
-		// Remove RevealScoreValues
-		<RevealScoreValues<T>>::remove(&key);
-
-		Ok(())
 	}
 
-   
-   pub(super) fn get_mean_value(key: SumTreeNameType<T>) -> i64 {
-	  let value = 	<IncentiveMeanRevealScore<T>>::get(key.clone());
-	  value
-   }
-
 
 	/// Calculate the mean of integer
 	pub(super) fn mean_integer(data: &Vec<i64>) -> Option<i64> {
@@ -217,6 +167,38 @@ impl<T: Config> Pallet<T> {
 		new_mean
 	}
 
+	/// Median of integer, mean of the two middle values for even count
+	pub(super) fn median_integer(data: &Vec<i64>) -> Option<i64> {
+		let mut sorted = data.clone();
+		sorted.sort();
+		let count = sorted.len();
+		match count {
+			0 => None,
+			odd if odd % 2 == 1 => Some(sorted[count / 2]),
+			_ => Some((sorted[count / 2 - 1] + sorted[count / 2]) / 2),
+		}
+	}
+
+	/// Mean of integer after dropping the lowest and highest quarter of the values
+	pub(super) fn interquartile_mean_integer(data: &Vec<i64>) -> Option<i64> {
+		let mut sorted = data.clone();
+		sorted.sort();
+		let trim = sorted.len() / 4;
+		let inner = sorted[trim..sorted.len() - trim].to_vec();
+		Self::mean_integer(&inner)
+	}
+
+	/// Mean of integer weighted by stake
+	pub(super) fn stake_weighted_mean_integer(data: &Vec<(i64, u64)>) -> Option<i64> {
+		let total_stake = data.iter().map(|(_, stake)| *stake as i128).sum::<i128>();
+		if total_stake == 0 {
+			return None
+		}
+		let weighted_sum =
+			data.iter().map(|(value, stake)| *value as i128 * *stake as i128).sum::<i128>();
+		Some((weighted_sum / total_stake) as i64)
+	}
+
 	pub(super) fn get_incentives_range(range_point: RangePoint) -> i64 {
 		match range_point {
 			RangePoint::ZeroToTen => 1500, //3 points,  1.5 ± mean, multiply by 1000 to make it integer
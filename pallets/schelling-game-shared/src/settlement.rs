@@ -0,0 +1,417 @@
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Settle one page of the drawn jurors of all rounds
+	/// Jurors are first counted into `WinnersIncentives` with `tally`, then given their
+	/// incentives or slashed with `settle`. At most `limit` jurors are tallied or settled in a call,
+	/// the progress is kept in `GameSettlement` for the next call
+	/// Settled jurors are stored in `JurorsIncentiveDistributedAccounts`, a juror is never
+	/// settled twice
+	/// Returns the number of jurors processed and whether all jurors are settled
+	pub(super) fn settle_jurors(
+		key: SumTreeNameType<T>,
+		limit: u32,
+		mut tally: impl FnMut(&mut WinnersIncentives, &AccountIdOf<T>, u64),
+		mut settle: impl FnMut(&WinnersIncentives, AccountIdOf<T>, u64) -> DispatchResult,
+	) -> Result<(u32, bool), DispatchError> {
+		let drawn_jurors = Self::get_all_rounds_drawn_jurors(key.clone());
+		let total = drawn_jurors.len() as u32;
+		let mut progress = <GameSettlement<T>>::get(&key);
+		let mut processed = 0;
+
+		while processed < limit && progress.tallied < total {
+			let (juror, stake) = &drawn_jurors[progress.tallied as usize];
+			tally(&mut progress.winners_incentives, juror, *stake);
+			progress.tallied += 1;
+			processed += 1;
+		}
+
+		while processed < limit && progress.tallied == total && progress.settled < total {
+			let (juror, stake) = drawn_jurors[progress.settled as usize].clone();
+			ensure!(
+				!<JurorsIncentiveDistributedAccounts<T>>::contains_key(&key, &juror),
+				Error::<T>::AlreadyGotIncentives
+			);
+			<JurorsIncentiveDistributedAccounts<T>>::insert(&key, &juror, ());
+			settle(&progress.winners_incentives, juror, stake)?;
+			progress.settled += 1;
+			processed += 1;
+		}
+
+		let all_settled = progress.tallied == total && progress.settled == total;
+		if all_settled {
+			<GameSettlement<T>>::remove(&key);
+			Self::deposit_event(Event::GameSettled { key });
+		} else {
+			<GameSettlement<T>>::insert(&key, progress);
+		}
+		Ok((processed, all_settled))
+	}
+
+	/// Settlement of the game has not started yet
+	pub(super) fn settlement_not_started(key: &SumTreeNameType<T>) -> bool {
+		!<GameSettlement<T>>::contains_key(key)
+	}
+
+	/// Count the juror of two choice schelling game into `winners_incentives`
+	pub(super) fn tally_two_choice_juror(
+		key: &SumTreeNameType<T>,
+		phase_data: &PhaseDataOf<T>,
+		winning_vote: &RevealedVote,
+		winners_incentives: &mut WinnersIncentives,
+		juror: &AccountIdOf<T>,
+		stake: u64,
+	) {
+		if let Some(vote) = <VoteCommits<T>>::get(key, juror).and_then(|c| c.revealed_vote) {
+			if vote == *winning_vote {
+				winners_incentives.winners_count += 1;
+				winners_incentives.winners_stake += stake;
+			} else {
+				winners_incentives.slashed_stake +=
+					Self::loser_slash_amount(phase_data, stake, None);
+			}
+		}
+	}
+
+	/// Give incentives to the juror of two choice schelling game, or slash the stake
+	pub(super) fn settle_two_choice_juror(
+		key: SumTreeNameType<T>,
+		phase_data: &PhaseDataOf<T>,
+		winning_decision: &WinningDecision,
+		winners_incentives: &WinnersIncentives,
+		juror: AccountIdOf<T>,
+		stake: u64,
+	) -> DispatchResult {
+		match <VoteCommits<T>>::get(&key, &juror) {
+			Some(commit_vote) => match (commit_vote.revealed_vote, winning_decision) {
+				(Some(RevealedVote::Yes), WinningDecision::WinnerYes)
+				| (Some(RevealedVote::No), WinningDecision::WinnerNo) => {
+					let winning_incentives =
+						Self::winner_incentives(phase_data, winners_incentives, stake);
+					Self::winner_getting_incentives(key, juror, winning_incentives, stake)
+				},
+				(Some(_), WinningDecision::Draw) => {
+					Self::getting_incentives_draw(key, juror, stake)
+				},
+				(Some(_), _) => Self::looser_getting_incentives(key, phase_data, juror, stake),
+				(None, _) => {
+					Self::non_participation_penalty(
+						key,
+						phase_data,
+						juror,
+						stake,
+						NonParticipation::NotRevealed,
+					);
+					Ok(())
+				},
+			},
+			None => {
+				Self::non_participation_penalty(
+					key,
+					phase_data,
+					juror,
+					stake,
+					NonParticipation::NotCommitted,
+				);
+				Ok(())
+			},
+		}
+	}
+
+	/// Settle at most `limit` jurors of two choice schelling game in execution period
+	/// Records the `GameResult` when settlement starts
+	/// Cleans the game storage once all jurors are settled
+	pub(super) fn settle_two_choice_helper(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		ensure!(!Self::game_settled(&key), Error::<T>::GameAlreadySettled);
+
+		let winning_decision = Self::get_winning_decision(<DecisionCount<T>>::get(&key));
+		if Self::settlement_not_started(&key) {
+			Self::deposit_event(Event::GameDecided {
+				key: key.clone(),
+				decision: winning_decision.clone(),
+			});
+			Self::conclude_game(
+				key.clone(),
+				GameOutcome::TwoChoice(winning_decision.clone()),
+				None,
+			);
+		}
+		let winning_vote = match winning_decision {
+			WinningDecision::WinnerYes => Some(RevealedVote::Yes),
+			WinningDecision::WinnerNo => Some(RevealedVote::No),
+			WinningDecision::Draw => None,
+		};
+
+		let (processed, all_settled) = Self::settle_jurors(
+			key.clone(),
+			limit,
+			|winners_incentives, juror, stake| {
+				if let Some(winning_vote) = &winning_vote {
+					Self::tally_two_choice_juror(
+						&key,
+						&phase_data,
+						winning_vote,
+						winners_incentives,
+						juror,
+						stake,
+					);
+				}
+			},
+			|winners_incentives, juror, stake| {
+				Self::settle_two_choice_juror(
+					key.clone(),
+					&phase_data,
+					&winning_decision,
+					winners_incentives,
+					juror,
+					stake,
+				)
+			},
+		)?;
+
+		if all_settled {
+			Self::finalize_game(key.clone());
+		}
+
+		Ok((processed, all_settled))
+	}
+
+	/// Settle at most `limit` jurors of score schelling game in execution period
+	/// The final score is aggregated with `score_aggregation` when settlement starts,
+	/// jurors within the incentives range of `range_point` from it are winners
+	/// If no score is revealed, the game is concluded as `GameOutcome::Undecided` and the jurors
+	/// are penalised for not committing or not revealing
+	/// Records the `GameResult` when settlement starts
+	/// Cleans the game storage once all jurors are settled
+	pub(super) fn settle_score_schelling_helper(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		range_point: RangePoint,
+		score_aggregation: ScoreAggregation,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		ensure!(!Self::game_settled(&key), Error::<T>::GameAlreadySettled);
+
+		let new_mean = if Self::settlement_not_started(&key) {
+			let revealed_scores = Self::get_revealed_scores(key.clone());
+			let new_mean = Self::aggregate_score(&score_aggregation, &revealed_scores);
+			match new_mean {
+				Some(new_mean) => {
+					<IncentiveMeanRevealScore<T>>::insert(key.clone(), new_mean);
+					Self::deposit_event(Event::ScoreGameDecided {
+						key: key.clone(),
+						score: new_mean,
+					});
+					Self::conclude_game(key.clone(), GameOutcome::Score, Some(new_mean));
+				},
+				None => Self::conclude_game(key.clone(), GameOutcome::Undecided, None),
+			}
+			new_mean
+		} else {
+			<GameResults<T>>::get(&key).and_then(|result| result.aggregated_score)
+		};
+		let incentives_range = Self::get_incentives_range(range_point);
+		// Distance of the score (multiplied by 1000) from the new mean, `None` on overflow
+		let distance =
+			|score: i64| score.checked_mul(1000).and_then(|score| score.checked_sub(new_mean?));
+		let in_range = |score: i64| {
+			distance(score).map_or(false, |distance| {
+				(-incentives_range..=incentives_range).contains(&distance)
+			})
+		};
+
+		let (processed, all_settled) = Self::settle_jurors(
+			key.clone(),
+			limit,
+			|winners_incentives, juror, stake| {
+				let revealed_vote =
+					<ScoreVoteCommits<T>>::get(&key, juror).and_then(|c| c.revealed_vote);
+				if let Some(score) = revealed_vote {
+					if in_range(score) {
+						winners_incentives.winners_count += 1;
+						winners_incentives.winners_stake += stake;
+					} else {
+						winners_incentives.slashed_stake +=
+							Self::loser_slash_amount(&phase_data, stake, distance(score));
+					}
+				}
+			},
+			|winners_incentives, juror, stake| {
+				match <ScoreVoteCommits<T>>::get(&key, &juror) {
+					Some(commit_vote) => match commit_vote.revealed_vote {
+						Some(score) if in_range(score) => {
+							// get incentives
+							let winning_incentives =
+								Self::winner_incentives(&phase_data, winners_incentives, stake);
+							Self::pay_incentives(
+								key.clone(),
+								juror,
+								stake,
+								winning_incentives,
+								true,
+							);
+						},
+						Some(score) => {
+							// deduct incentives, scaled by the distance from the new mean
+							let distance = distance(score);
+							Self::looser_slash(key.clone(), &phase_data, juror, stake, distance);
+						},
+						None => Self::non_participation_penalty(
+							key.clone(),
+							&phase_data,
+							juror,
+							stake,
+							NonParticipation::NotRevealed,
+						),
+					},
+					None => Self::non_participation_penalty(
+						key.clone(),
+						&phase_data,
+						juror,
+						stake,
+						NonParticipation::NotCommitted,
+					),
+				}
+				Ok(())
+			},
+		)?;
+
+		if all_settled {
+			Self::finalize_game(key.clone());
+
+			// Remove RevealScoreValues
+			<RevealScoreValues<T>>::remove(&key);
+			<RevealScoreStakes<T>>::remove(&key);
+		}
+
+		Ok((processed, all_settled))
+	}
+
+	/// Settle at most `limit` jurors of multi choice schelling game in execution period
+	/// Records the `GameResult` when settlement starts
+	/// Cleans the game storage once all jurors are settled
+	pub(super) fn settle_multi_choice_helper(
+		key: SumTreeNameType<T>,
+		phase_data: PhaseDataOf<T>,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		match <PeriodName<T>>::get(&key) {
+			Some(period) => {
+				ensure!(period == Period::Execution, Error::<T>::PeriodDontMatch);
+			},
+			None => Err(Error::<T>::PeriodDoesNotExists)?,
+		}
+		ensure!(!Self::game_settled(&key), Error::<T>::GameAlreadySettled);
+
+		let winning_option = Self::get_winning_option(key.clone());
+		if Self::settlement_not_started(&key) {
+			Self::deposit_event(Event::MultiChoiceGameDecided {
+				key: key.clone(),
+				decision: winning_option.clone(),
+			});
+			Self::conclude_game(
+				key.clone(),
+				GameOutcome::MultiChoice(winning_option.clone()),
+				None,
+			);
+		}
+
+		let (processed, all_settled) = Self::settle_jurors(
+			key.clone(),
+			limit,
+			|winners_incentives, juror, stake| {
+				if let WinningOption::Winner(option) = winning_option {
+					match <MultiChoiceVoteCommits<T>>::get(&key, juror)
+						.and_then(|c| c.revealed_vote)
+					{
+						Some(vote) if vote == option => {
+							winners_incentives.winners_count += 1;
+							winners_incentives.winners_stake += stake;
+						},
+						Some(_) => {
+							winners_incentives.slashed_stake +=
+								Self::loser_slash_amount(&phase_data, stake, None);
+						},
+						None => {},
+					}
+				}
+			},
+			|winners_incentives, juror, stake| {
+				match <MultiChoiceVoteCommits<T>>::get(&key, &juror) {
+					Some(commit_vote) => {
+						match (commit_vote.revealed_vote, winning_option.clone()) {
+							(Some(vote), WinningOption::Winner(option)) => {
+								if vote == option {
+									let winning_incentives = Self::winner_incentives(
+										&phase_data,
+										winners_incentives,
+										stake,
+									);
+									Self::pay_incentives(
+										key.clone(),
+										juror,
+										stake,
+										winning_incentives,
+										true,
+									);
+								} else {
+									Self::looser_slash(
+										key.clone(),
+										&phase_data,
+										juror,
+										stake,
+										None,
+									);
+								}
+							},
+							(Some(_), WinningOption::Draw) => {
+								Self::pay_incentives(key.clone(), juror, stake, 0, false);
+							},
+							(None, _) => {
+								Self::non_participation_penalty(
+									key.clone(),
+									&phase_data,
+									juror,
+									stake,
+									NonParticipation::NotRevealed,
+								);
+							},
+						}
+					},
+					None => {
+						Self::non_participation_penalty(
+							key.clone(),
+							&phase_data,
+							juror,
+							stake,
+							NonParticipation::NotCommitted,
+						);
+					},
+				}
+				Ok(())
+			},
+		)?;
+
+		if all_settled {
+			Self::finalize_game(key.clone());
+		}
+
+		Ok((processed, all_settled))
+	}
+}
@@ -9,25 +9,41 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 	type AccountId = AccountIdOf<T>;
 	type Balance = BalanceOf<T>;
 	type RangePoint = RangePoint;
+	type ScoreAggregation = ScoreAggregation;
 	type Period = Period;
 	type PhaseData = PhaseDataOf<T>;
+	type PhaseLengths = PhaseLengths;
+	type GameResult = GameResult<BlockNumberOf<T>>;
 
+	/// Phase data with a length for each period, validated with `PhaseData::is_valid`
 	fn create_phase_data(
-		block_length: u64,
+		phase_lengths: Self::PhaseLengths,
 		max_draws: u64,
 		min_number_juror_staked: u64,
 		min_juror_stake: u64,
 		juror_incentives: (u64, u64),
-	) -> Self::PhaseData {
+		max_appeals: u32,
+	) -> Result<Self::PhaseData, DispatchError> {
 		Self::create_phase_data(
-			block_length,
+			phase_lengths,
 			max_draws,
 			min_number_juror_staked,
 			min_juror_stake,
 			juror_incentives,
+			max_appeals,
 		)
 	}
 
+	/// Phase data of the schelling game type set by `PhaseDataOrigin`
+	fn get_phase_data_link(game_type: Self::SchellingGameType) -> Option<Self::PhaseData> {
+		Self::game_type_phase_data(game_type)
+	}
+
+	/// Phase data of the game, stored when the game starts
+	fn get_game_phase_data_link(key: Self::SumTreeName) -> Option<Self::PhaseData> {
+		Self::game_phase_data(key)
+	}
+
 	/// Get the Period
 	fn get_period_link(key: Self::SumTreeName) -> Option<Period> {
 		Self::get_period(key)
@@ -35,12 +51,13 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 
 	/// Set `PeriodName` to `Period::Evidence`
 	/// Called with submission of `Evidence` stake e.g. Profile stake
-	/// Also set `EvidenceStartTime`    
+	/// Also set `EvidenceStartTime` and the phase data of the game
 	fn set_to_evidence_period_link(
 		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
 		now: Self::BlockNumber,
 	) -> DispatchResult {
-		Self::set_to_evidence_period(key, now)
+		Self::set_to_evidence_period(key, phase_data, now)
 	}
 
 	/// Create a sortition sum tree   
@@ -65,11 +82,14 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 		Self::set_to_staking_period(key, phase_data, now)
 	}
 
+	/// Set staking period for no evidence period
+	/// Change of staking period to drawing period is scheduled at the end of staking period
 	fn set_to_staking_period_pe_link(
 		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
 		now: Self::BlockNumber,
 	) -> DispatchResult {
-		Self::set_to_staking_period_pe(key, now)
+		Self::set_to_staking_period_pe(key, phase_data, now)
 	}
 
 	/// Change the `Period`
@@ -83,7 +103,8 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 	/// ```
 	///
 	///  `Period::Drawing` to `Period::Commit`   
-	/// When maximum juror are drawn   
+	/// When maximum juror are drawn, or when drawing period is over and at least `min_draws`
+	/// jurors are drawn, otherwise the game is cancelled   
 	///  
 	/// `Period::Commit` to `Period::Vote`       
 	/// ```ignore
@@ -92,10 +113,17 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 	/// }
 	/// ```
 	///
-	/// `Period::Vote` to `Period::Execution`   
+	/// `Period::Vote` to `Period::Appeal`, or to `Period::Execution` when `max_appeals` is reached   
 	/// ```ignore
 	/// if now >= min_long_block_length + vote_start_time {
-	///   // Change `Period::Vote` to `Period::Execution`   
+	///   // Change `Period::Vote` to `Period::Appeal`   
+	/// }
+	/// ```   
+	///
+	/// `Period::Appeal` to `Period::Execution`   
+	/// ```ignore
+	/// if now >= appeal_length + appeal_start_time {
+	///   // Change `Period::Appeal` to `Period::Execution`   
 	/// }
 	/// ```   
 	fn change_period_link(
@@ -156,18 +184,6 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 	) -> DispatchResult {
 		Self::reveal_vote_two_choice_helper(key, who, choice, salt)
 	}
-	/// Distribute incentives for two choices        
-	/// Winner gets `stake` + `winning_incentives`      
-	/// If decision is draw, jurors receive their `stake`    
-	/// Lost jurors gets `stake * 3/4`   
-	/// When they receive their incentives, their accountid is stored in `JurorsIncentiveDistributedAccounts`        
-	fn get_incentives_two_choice_helper_link(
-		key: Self::SumTreeName,
-		phase_data: Self::PhaseData,
-		who: Self::AccountId,
-	) -> DispatchResult {
-		Self::get_incentives_two_choice_helper(key, phase_data, who)
-	}
 
 	/// Blocks left for ending evidence period
 	/// When evidence time ends, you can submit the challenge stake    
@@ -215,6 +231,30 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 		Self::get_vote_period_end_block_helper(key, phase_data, now)
 	}
 
+	/// Blocks left for ending appeal period
+	fn get_appeal_period_end_block_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		now: Self::BlockNumber,
+	) -> Option<u32> {
+		Self::get_appeal_period_end_block_helper(key, phase_data, now)
+	}
+
+	/// Appeal the ruling   
+	/// Ensure `Period` is `Appeal`   
+	/// `appellant_choice` is the choice the appellant stands for, it must not have won the round   
+	/// Takes `appeal_fee` and starts a new round with `2n + 1` jurors in `Period::Staking`   
+	/// Jurors of all rounds are given incentives in `Period::Execution`   
+	fn appeal_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		who: Self::AccountId,
+		appellant_choice: Option<u32>,
+		now: Self::BlockNumber,
+	) -> DispatchResult {
+		Self::appeal_helper(key, phase_data, who, appellant_choice, now)
+	}
+
 	/// Check if `AccountId` is selected as juror
 	fn selected_as_juror_helper_link(key: Self::SumTreeName, who: Self::AccountId) -> bool {
 		Self::selected_as_juror_helper(key, who)
@@ -239,29 +279,93 @@ impl<T: Config> SchellingGameSharedLink for Pallet<T> {
 		Self::reveal_vote_score_helper(key, who, choice, salt)
 	}
 
-	/// Distribute incentives to all score schelling game jurors
-	fn get_incentives_score_schelling_helper_link(
+	/// Get new mean in score schelling game
+	fn get_mean_value_link(key: Self::SumTreeName) -> i64 {
+		Self::get_mean_value(key)
+	}
+
+	fn get_drawn_jurors(key: Self::SumTreeName) -> Vec<(Self::AccountId, u64)> {
+		Self::drawn_jurors(key).into_inner()
+	}
+
+	/// Set the number of options of a multi choice schelling game
+	/// Call it in `Period::Evidence` or `Period::Staking`, it can not be changed once set
+	fn set_number_of_options_link(
 		key: Self::SumTreeName,
-		phase_data: Self::PhaseData,
-		range_point: Self::RangePoint,
+		number_of_options: u32,
 	) -> DispatchResult {
-		Self::get_incentives_score_schelling_helper(key, phase_data, range_point)
+		Self::set_number_of_options_helper(key, number_of_options)
 	}
 
-	/// Get new mean in score schelling game
-	fn get_mean_value_link(key: Self::SumTreeName) -> i64 {
-		Self::get_mean_value(key)
+	/// Commit vote of multi choice schelling game
+	fn commit_vote_multi_choice_helper_link(
+		key: Self::SumTreeName,
+		who: Self::AccountId,
+		vote_commit: [u8; 32],
+	) -> DispatchResult {
+		Self::commit_vote_multi_choice_helper(key, who, vote_commit)
 	}
 
-	/// Distribute incentives to all two choice shelling game jurors
-	fn get_all_incentives_two_choice_helper(
+	/// Reveal vote of multi choice schelling game
+	/// `choice` is the index of the option, and must be less than the number of options set
+	/// with `set_number_of_options_link`
+	fn reveal_vote_multi_choice_helper_link(
 		key: Self::SumTreeName,
-		phase_data: Self::PhaseData,
+		who: Self::AccountId,
+		choice: u32,
+		salt: Vec<u8>,
 	) -> DispatchResult {
-		Self::get_all_incentives_two_choice_helper(key, phase_data)
+		Self::reveal_vote_multi_choice_helper(key, who, choice, salt)
 	}
 
-	fn get_drawn_jurors(key: Self::SumTreeName) -> Vec<(Self::AccountId, u64)> {
-		Self::drawn_jurors(key)
+	/// Option with the highest count of votes, `None` in case of draw
+	fn get_winning_option_link(key: Self::SumTreeName) -> Option<u32> {
+		Self::get_winning_option_helper(key)
+	}
+
+	/// Settle at most `limit` jurors of two choice schelling game
+	/// Returns the number of jurors processed and whether all jurors are settled
+	fn settle_two_choice_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		Self::settle_two_choice_helper(key, phase_data, limit)
+	}
+
+	/// Settle at most `limit` jurors of score schelling game
+	/// Returns the number of jurors processed and whether all jurors are settled
+	fn settle_score_schelling_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		range_point: Self::RangePoint,
+		score_aggregation: Self::ScoreAggregation,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		Self::settle_score_schelling_helper(key, phase_data, range_point, score_aggregation, limit)
+	}
+
+	/// Settle at most `limit` jurors of multi choice schelling game
+	/// Returns the number of jurors processed and whether all jurors are settled
+	fn settle_multi_choice_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError> {
+		Self::settle_multi_choice_helper(key, phase_data, limit)
+	}
+
+	/// Result of the game, recorded when the game is decided
+	/// Kept after the game storage is pruned
+	fn get_result_link(key: Self::SumTreeName) -> Option<Self::GameResult> {
+		Self::game_result(key)
+	}
+
+	/// Cancel a game that is not decided yet
+	/// Stakes of all jurors are refunded, the sortition sum tree is removed and the result is
+	/// recorded with `GameOutcome::Cancelled`, `OnGameConcluded` refunds the deposits held by the
+	/// pallet that created the game
+	fn cancel_game_link(key: Self::SumTreeName) -> DispatchResult {
+		Self::cancel_game_helper(key).map(|_| ())
 	}
 }
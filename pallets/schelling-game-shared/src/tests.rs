@@ -1,10 +1,25 @@
 use crate::{
+	commit::{COMMIT_DOMAIN, COMMIT_VERSION},
+	migrations::v1,
 	mock::*,
-	types::{Period, PhaseData, RangePoint, SchellingGameType},
-	Error, Event,
+	randomness::{DrawRandomness, FutureBlockRandomness, VrfRandomness},
+	types::{
+		CommitChoice, GameOutcome, GameResult, JurorStats, NonParticipation, Period, PhaseData,
+		PhaseLengths, RangePoint, RewardCurve, SchellingGameType, ScoreAggregation,
+		WinningDecision, WinningOption,
+	},
+	weights::WeightInfo,
+	Error, Event, JurorsIncentiveDistributedAccounts, PeriodName, PruneQueue, UnstakedJurors,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::Encode,
+	sp_runtime::{DispatchError, Percent},
+	storage::unhashed,
+	traits::{ConstU64, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
 };
-use frame_support::{assert_noop, assert_ok};
 
+use schelling_game_shared_link::SchellingGameSharedLink;
 use sortition_sum_game::types::SumTreeName;
 
 type CitizenId = u64;
@@ -19,10 +34,14 @@ fn return_game_type_profile_approval() -> SchellingGameType {
 }
 
 fn get_the_phase_data() -> PhaseData<Test> {
-	let data = PhaseData::create_with_data(50, 5, 3, 100, (100, 100));
+	let data = PhaseData::create_with_data(PhaseLengths::uniform(50), 5, 3, 100, (100, 100), 2);
 	data
 }
 
+fn commit(key: &SumTreeName<u64, u64>, juror: u64, choice: CommitChoice, salt: &str) -> [u8; 32] {
+	TemplateModule::commit_hash(key, &juror, &choice, salt.as_bytes())
+}
+
 #[test]
 fn it_works_for_default_value() {
 	new_test_ext().execute_with(|| {
@@ -53,7 +72,7 @@ fn evidence_period_not_over_test() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let phase_data = get_the_phase_data();
 		let now2 = now + phase_data.evidence_length - 1;
@@ -72,7 +91,7 @@ fn evidence_period_test() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let phase_data = get_the_phase_data();
 		let now2 = now + phase_data.evidence_length;
@@ -87,7 +106,7 @@ fn apply_juror() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let phase_data = get_the_phase_data();
 		let now2 = now + phase_data.evidence_length;
@@ -115,7 +134,7 @@ fn challenger_win_test() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let phase_data = get_the_phase_data();
 
@@ -148,23 +167,23 @@ fn challenger_win_test() {
 		let draws_in_round = TemplateModule::draws_in_round(key.clone());
 		assert_eq!(5, draws_in_round);
 		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
-		assert_eq!(vec![(4, 400), (7, 700), (13, 1300), (14, 1400), (15, 1500)], drawn_jurors);
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let balance = Balances::free_balance(5);
 		assert_eq!(299500, balance);
 		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
 		let balance = Balances::free_balance(5);
 		assert_eq!(300000, balance);
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 4, hash));
-		let hash = sp_io::hashing::keccak_256("1salt2".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 7, hash));
-		let hash = sp_io::hashing::keccak_256("1salt3".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 13, hash));
-		let hash = sp_io::hashing::keccak_256("1salt4".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 14, hash));
-		let hash = sp_io::hashing::keccak_256("0salt5".as_bytes());
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(1), "salt2");
 		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(1), "salt3");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(1), "salt4");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::TwoChoice(0), "salt5");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 22, hash));
 		let commit_start_time = TemplateModule::commit_start_time(key.clone());
 		let new_now = commit_start_time + phase_data.commit_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
@@ -172,31 +191,31 @@ fn challenger_win_test() {
 		assert_eq!(Some(Period::Vote), period);
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			4,
+			12,
 			1,
 			"salt".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			7,
+			15,
 			1,
 			"salt2".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			13,
+			19,
 			1,
 			"salt3".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			14,
+			20,
 			1,
 			"salt4".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			15,
+			22,
 			0,
 			"salt5".as_bytes().to_vec()
 		));
@@ -206,54 +225,49 @@ fn challenger_win_test() {
 		let new_now = vote_start_time + phase_data.vote_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
 		assert_eq!(Some(Period::Execution), period);
 
-		let balance = Balances::free_balance(4);
-		assert_eq!(299600, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			4
-		));
-		let balance = Balances::free_balance(4);
+		let balance = Balances::free_balance(12);
+		assert_eq!(298800, balance);
+		// Tally the 5 drawn jurors, then settle one juror in each call
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 5),
+			Ok((5, false))
+		);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(12);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(7);
+		let balance = Balances::free_balance(15);
 		// println!("{:?}", balance);
-		assert_eq!(299300, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			7
-		));
-		let balance = Balances::free_balance(7);
+		assert_eq!(298500, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(15);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(13);
-		assert_eq!(298700, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			13
-		));
-		let balance = Balances::free_balance(13);
+		let balance = Balances::free_balance(19);
+		assert_eq!(298100, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(19);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(14);
-		assert_eq!(298600, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			14
-		));
-		let balance = Balances::free_balance(14);
+		let balance = Balances::free_balance(20);
+		assert_eq!(298000, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(20);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(15);
-		assert_eq!(298500, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			15
-		));
-		let balance = Balances::free_balance(15);
-		assert_eq!(299625, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(297800, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(22);
+		assert_eq!(299450, balance);
+		assert!(JurorsIncentiveDistributedAccounts::<Test>::contains_key(&key, 22));
+		assert_noop!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1),
+			Error::<Test>::GameAlreadySettled
+		);
 	});
 }
 
@@ -262,7 +276,7 @@ fn challenger_win_test_jurors_incentive_in_one_go() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let game_type = return_game_type_profile_approval();
 
@@ -300,23 +314,23 @@ fn challenger_win_test_jurors_incentive_in_one_go() {
 		let draws_in_round = TemplateModule::draws_in_round(key.clone());
 		assert_eq!(5, draws_in_round);
 		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
-		assert_eq!(vec![(4, 400), (7, 700), (13, 1300), (14, 1400), (15, 1500)], drawn_jurors);
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let balance = Balances::free_balance(5);
 		assert_eq!(299500, balance);
 		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
 		let balance = Balances::free_balance(5);
 		assert_eq!(300000, balance);
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 4, hash));
-		let hash = sp_io::hashing::keccak_256("1salt2".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 7, hash));
-		let hash = sp_io::hashing::keccak_256("1salt3".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 13, hash));
-		let hash = sp_io::hashing::keccak_256("1salt4".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 14, hash));
-		let hash = sp_io::hashing::keccak_256("0salt5".as_bytes());
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(1), "salt2");
 		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(1), "salt3");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(1), "salt4");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::TwoChoice(0), "salt5");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 22, hash));
 		let commit_start_time = TemplateModule::commit_start_time(key.clone());
 		let new_now = commit_start_time + phase_data.commit_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
@@ -324,31 +338,31 @@ fn challenger_win_test_jurors_incentive_in_one_go() {
 		assert_eq!(Some(Period::Vote), period);
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			4,
+			12,
 			1,
 			"salt".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			7,
+			15,
 			1,
 			"salt2".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			13,
+			19,
 			1,
 			"salt3".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			14,
+			20,
 			1,
 			"salt4".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			15,
+			22,
 			0,
 			"salt5".as_bytes().to_vec()
 		));
@@ -358,32 +372,45 @@ fn challenger_win_test_jurors_incentive_in_one_go() {
 		let new_now = vote_start_time + phase_data.vote_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
 		assert_eq!(Some(Period::Execution), period);
-		let balance = Balances::free_balance(4);
-		assert_eq!(299600, balance);
-		let balance = Balances::free_balance(7);
-		// println!("{:?}", balance);
-		assert_eq!(299300, balance);
-		let balance = Balances::free_balance(13);
-		assert_eq!(298700, balance);
-		let balance = Balances::free_balance(14);
-		assert_eq!(298600, balance);
+		let balance = Balances::free_balance(12);
+		assert_eq!(298800, balance);
 		let balance = Balances::free_balance(15);
+		// println!("{:?}", balance);
 		assert_eq!(298500, balance);
-		assert_ok!(TemplateModule::get_all_incentives_two_choice_helper(
+		let balance = Balances::free_balance(19);
+		assert_eq!(298100, balance);
+		let balance = Balances::free_balance(20);
+		assert_eq!(298000, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(297800, balance);
+		assert_eq!(1200, Balances::reserved_balance(12));
+		assert_eq!(2200, Balances::reserved_balance(22));
+		let total_issuance = Balances::total_issuance();
+		assert_ok!(TemplateModule::settle_two_choice_helper(
 			key.clone(),
-			phase_data.clone()
+			phase_data.clone(),
+			u32::MAX
 		));
-		let balance = Balances::free_balance(4);
+		let balance = Balances::free_balance(12);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(7);
+		let balance = Balances::free_balance(15);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(13);
+		let balance = Balances::free_balance(19);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(14);
+		let balance = Balances::free_balance(20);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(15);
-		assert_eq!(299625, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(299450, balance);
+		assert_eq!(0, Balances::reserved_balance(12));
+		assert_eq!(0, Balances::reserved_balance(22));
+		// Incentives of 100 minted, 550 of the stake of juror 22 slashed
+		assert_eq!(total_issuance + 100 - 550, Balances::total_issuance());
 	});
 }
 
@@ -392,7 +419,7 @@ fn challenger_lost_test() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		// let game_type = return_game_type_profile_approval();
 		let phase_data = get_the_phase_data();
@@ -428,23 +455,23 @@ fn challenger_lost_test() {
 		let draws_in_round = TemplateModule::draws_in_round(key.clone());
 		assert_eq!(5, draws_in_round);
 		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
-		assert_eq!(vec![(4, 400), (7, 700), (13, 1300), (14, 1400), (15, 1500)], drawn_jurors);
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let balance = Balances::free_balance(5);
 		assert_eq!(299500, balance);
 		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
 		let balance = Balances::free_balance(5);
 		assert_eq!(300000, balance);
-		let hash = sp_io::hashing::keccak_256("0salt".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 4, hash));
-		let hash = sp_io::hashing::keccak_256("0salt2".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 7, hash));
-		let hash = sp_io::hashing::keccak_256("0salt3".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 13, hash));
-		let hash = sp_io::hashing::keccak_256("0salt4".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 14, hash));
-		let hash = sp_io::hashing::keccak_256("1salt5".as_bytes());
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(0), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(0), "salt2");
 		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(0), "salt3");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(0), "salt4");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::TwoChoice(1), "salt5");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 22, hash));
 		let commit_start_time = TemplateModule::commit_start_time(key.clone());
 		let new_now = commit_start_time + phase_data.commit_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
@@ -452,31 +479,31 @@ fn challenger_lost_test() {
 		assert_eq!(Some(Period::Vote), period);
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			4,
+			12,
 			0,
 			"salt".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			7,
+			15,
 			0,
 			"salt2".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			13,
+			19,
 			0,
 			"salt3".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			14,
+			20,
 			0,
 			"salt4".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
 			key.clone(),
-			15,
+			22,
 			1,
 			"salt5".as_bytes().to_vec()
 		));
@@ -486,54 +513,44 @@ fn challenger_lost_test() {
 		let new_now = vote_start_time + phase_data.vote_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
 		assert_eq!(Some(Period::Execution), period);
 
-		let balance = Balances::free_balance(4);
-		assert_eq!(299600, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			4
-		));
-		let balance = Balances::free_balance(4);
+		let balance = Balances::free_balance(12);
+		assert_eq!(298800, balance);
+		// Tally the 5 drawn jurors, then settle one juror in each call
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 5),
+			Ok((5, false))
+		);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(12);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(7);
+		let balance = Balances::free_balance(15);
 		// println!("{:?}", balance);
-		assert_eq!(299300, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			7
-		));
-		let balance = Balances::free_balance(7);
+		assert_eq!(298500, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(15);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(13);
-		assert_eq!(298700, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			13
-		));
-		let balance = Balances::free_balance(13);
+		let balance = Balances::free_balance(19);
+		assert_eq!(298100, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(19);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(14);
-		assert_eq!(298600, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			14
-		));
-		let balance = Balances::free_balance(14);
+		let balance = Balances::free_balance(20);
+		assert_eq!(298000, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(20);
 		assert_eq!(300025, balance);
-		let balance = Balances::free_balance(15);
-		assert_eq!(298500, balance);
-		assert_ok!(TemplateModule::get_incentives_two_choice_helper(
-			key.clone(),
-			phase_data.clone(),
-			15
-		));
-		let balance = Balances::free_balance(15);
-		assert_eq!(299625, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(297800, balance);
+		assert_ok!(TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 1));
+		let balance = Balances::free_balance(22);
+		assert_eq!(299450, balance);
 	});
 }
 
@@ -542,7 +559,7 @@ fn score_schelling_game_test() {
 	new_test_ext().execute_with(|| {
 		let key = return_key_profile(0);
 		let now = 10;
-		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), now));
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
 		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Evidence);
 		let game_type = return_game_type_profile_approval();
 		// let min_short_block_length = return_min_short_block_length();
@@ -578,23 +595,23 @@ fn score_schelling_game_test() {
 		let draws_in_round = TemplateModule::draws_in_round(key.clone());
 		assert_eq!(5, draws_in_round);
 		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
-		assert_eq!(vec![(4, 400), (7, 700), (13, 1300), (14, 1400), (15, 1500)], drawn_jurors);
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let balance = Balances::free_balance(5);
 		assert_eq!(299500, balance);
 		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
 		let balance = Balances::free_balance(5);
 		assert_eq!(300000, balance);
-		let hash = sp_io::hashing::keccak_256("1salt".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 4, hash));
-		let hash = sp_io::hashing::keccak_256("1salt2".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 7, hash));
-		let hash = sp_io::hashing::keccak_256("5salt3".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 13, hash));
-		let hash = sp_io::hashing::keccak_256("1salt4".as_bytes());
-		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 14, hash));
-		let hash = sp_io::hashing::keccak_256("7salt5".as_bytes());
+		let hash = commit(&key, 12, CommitChoice::Score(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::Score(1), "salt2");
 		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::Score(5), "salt3");
+		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::Score(1), "salt4");
+		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::Score(7), "salt5");
+		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 22, hash));
 		let commit_start_time = TemplateModule::commit_start_time(key.clone());
 		let new_now = commit_start_time + phase_data.commit_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
@@ -602,32 +619,32 @@ fn score_schelling_game_test() {
 		assert_eq!(Some(Period::Vote), period);
 		assert_ok!(TemplateModule::reveal_vote_score_helper(
 			key.clone(),
-			4,
+			12,
 			1,
 			"salt".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_score_helper(
 			key.clone(),
-			7,
+			15,
 			1,
 			"salt2".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_score_helper(
 			key.clone(),
-			13,
+			19,
 			5,
 			"salt3".as_bytes().to_vec()
 		));
 		assert_ok!(TemplateModule::reveal_vote_score_helper(
 			key.clone(),
-			14,
+			20,
 			1,
 			"salt4".as_bytes().to_vec()
 		));
 		assert_noop!(
 			TemplateModule::reveal_vote_score_helper(
 				key.clone(),
-				15,
+				22,
 				8,
 				"salt5".as_bytes().to_vec()
 			),
@@ -635,7 +652,7 @@ fn score_schelling_game_test() {
 		);
 		assert_ok!(TemplateModule::reveal_vote_score_helper(
 			key.clone(),
-			15,
+			22,
 			7,
 			"salt5".as_bytes().to_vec()
 		));
@@ -643,37 +660,1747 @@ fn score_schelling_game_test() {
 		let new_now = vote_start_time + phase_data.commit_length;
 		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
 		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
 		assert_eq!(Some(Period::Execution), period);
 		let reveal_score = TemplateModule::reveal_score_values(key.clone());
-		assert_eq!(vec![1000, 1000, 5000, 1000, 7000], reveal_score);
-		let balance = Balances::free_balance(4);
-		assert_eq!(299600, balance);
-		let balance = Balances::free_balance(7);
-		// println!("{:?}", balance);
-		assert_eq!(299300, balance);
-		let balance = Balances::free_balance(13);
-		assert_eq!(298700, balance);
-		let balance = Balances::free_balance(14);
-		assert_eq!(298600, balance);
+		assert_eq!(reveal_score, vec![1000, 1000, 5000, 1000, 7000]);
+		let balance = Balances::free_balance(12);
+		assert_eq!(298800, balance);
 		let balance = Balances::free_balance(15);
+		// println!("{:?}", balance);
 		assert_eq!(298500, balance);
-		assert_ok!(TemplateModule::get_incentives_score_schelling_helper(
+		let balance = Balances::free_balance(19);
+		assert_eq!(298100, balance);
+		let balance = Balances::free_balance(20);
+		assert_eq!(298000, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(297800, balance);
+		assert_ok!(TemplateModule::settle_score_schelling_helper(
 			key.clone(),
 			phase_data.clone(),
-			RangePoint::ZeroToTen
+			RangePoint::ZeroToTen,
+			ScoreAggregation::MeanWithinStdDev,
+			u32::MAX
 		));
 		let mean_values = TemplateModule::new_mean_reveal_score(key.clone());
 		assert_eq!(2000, mean_values);
-		let balance = Balances::free_balance(4);
+		let balance = Balances::free_balance(12);
 		// println!("{:?}", balance);
 		assert_eq!(300033, balance);
-		let balance = Balances::free_balance(7);
+		let balance = Balances::free_balance(15);
 		assert_eq!(300033, balance);
-		let balance = Balances::free_balance(13); // Balance deducted as voted 5
-		assert_eq!(299675, balance);
-		let balance = Balances::free_balance(14);
+		let balance = Balances::free_balance(19); // Balance deducted as voted 5
+		assert_eq!(299525, balance);
+		let balance = Balances::free_balance(20);
 		assert_eq!(300033, balance);
-		let balance = Balances::free_balance(15); // Balance deducted as voted 7
-		assert_eq!(299625, balance);
+		let balance = Balances::free_balance(22); // Balance deducted as voted 7
+		assert_eq!(299450, balance);
+	});
+}
+
+#[test]
+fn appeal_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let phase_data = get_the_phase_data();
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(1), "salt2");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(1), "salt3");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(1), "salt4");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::TwoChoice(0), "salt5");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 22, hash));
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			12,
+			1,
+			"salt".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			15,
+			1,
+			"salt2".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			19,
+			1,
+			"salt3".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			20,
+			1,
+			"salt4".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			22,
+			0,
+			"salt5".as_bytes().to_vec()
+		));
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+
+		// Side that won the round can not appeal
+		assert_noop!(
+			TemplateModule::appeal_helper(key.clone(), phase_data.clone(), 1, Some(1), new_now),
+			Error::<Test>::NotLosingParty
+		);
+
+		// Appeal opens a new round with 2n + 1 jurors
+		let balance = Balances::free_balance(1);
+		assert_eq!(100000, balance);
+		assert_ok!(TemplateModule::appeal_helper(
+			key.clone(),
+			phase_data.clone(),
+			1,
+			Some(0),
+			new_now
+		));
+		let balance = Balances::free_balance(1);
+		assert_eq!(100000 - 500, balance);
+		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Staking), period);
+		assert_eq!(1, TemplateModule::appeal_round(key.clone()));
+		assert_eq!(
+			vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)],
+			TemplateModule::past_round_drawn_jurors(key.clone(), 0).into_inner()
+		);
+		assert_noop!(
+			TemplateModule::apply_jurors_helper(key.clone(), phase_data.clone(), 12, 1200),
+			Error::<Test>::DrawnInPreviousRound
+		);
+		// Juror who unstaked in the first round can not stake again
+		assert_noop!(
+			TemplateModule::apply_jurors_helper(key.clone(), phase_data.clone(), 5, 500),
+			Error::<Test>::AlreadyUnstaked
+		);
+
+		let new_now = new_now + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_noop!(
+			TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()),
+			Error::<Test>::MaxJurorNotDrawn
+		);
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 11));
+		assert_eq!(11, TemplateModule::draws_in_round(key.clone()));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(11, drawn_jurors.len());
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_noop!(
+			TemplateModule::commit_vote_helper(key.clone(), 12, hash),
+			Error::<Test>::JurorDoesNotExists
+		);
+		for (juror, _) in drawn_jurors.iter() {
+			let hash = commit(&key, *juror, CommitChoice::TwoChoice(1), &format!("salt{}", juror));
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), *juror, hash));
+		}
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		for (juror, _) in drawn_jurors.iter() {
+			assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				*juror,
+				1,
+				format!("salt{}", juror).as_bytes().to_vec()
+			));
+		}
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Appeal), period);
+		let new_now = new_now + phase_data.appeal_length;
+		assert_noop!(
+			TemplateModule::appeal_helper(key.clone(), phase_data.clone(), 1, Some(0), new_now),
+			Error::<Test>::AppealPeriodOver
+		);
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let period = TemplateModule::get_period(key.clone());
+		assert_eq!(Some(Period::Execution), period);
+
+		// 15 jurors voted for the final decision in both rounds, 100 / 15 = 6
+		assert_ok!(TemplateModule::settle_two_choice_helper(
+			key.clone(),
+			phase_data.clone(),
+			u32::MAX
+		));
+		let balance = Balances::free_balance(12);
+		assert_eq!(300006, balance);
+		let balance = Balances::free_balance(22);
+		assert_eq!(299450, balance);
+		for (juror, _) in drawn_jurors.iter() {
+			let balance = Balances::free_balance(juror);
+			assert_eq!(300006, balance);
+		}
+	});
+}
+
+#[test]
+fn automatic_period_change_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let phase_data = get_the_phase_data();
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let staking_end = staking_start_time + phase_data.staking_length;
+		assert_eq!(TemplateModule::period_change_queue(staking_end), vec![key.clone()]);
+		TemplateModule::on_initialize(staking_end - 1);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Staking);
+		TemplateModule::on_initialize(staking_end);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Drawing);
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Drawing }.into(),
+		);
+		assert!(TemplateModule::period_change_queue(staking_end).is_empty());
+
+		System::set_block_number(staking_end);
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		let commit_start_time = staking_end + 1;
+		TemplateModule::on_initialize(commit_start_time);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Commit);
+		assert_eq!(TemplateModule::commit_start_time(key.clone()), commit_start_time);
+
+		let vote_start_time = commit_start_time + phase_data.commit_length;
+		TemplateModule::on_initialize(vote_start_time);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Vote);
+
+		let appeal_start_time = vote_start_time + phase_data.vote_length;
+		TemplateModule::on_initialize(appeal_start_time);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Appeal);
+
+		TemplateModule::on_initialize(appeal_start_time + phase_data.appeal_length);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Execution);
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Execution }.into(),
+		);
+		assert_eq!(TemplateModule::game_phase_data(key.clone()), Some(phase_data));
+	});
+}
+
+#[test]
+fn period_change_overflow_test() {
+	new_test_ext().execute_with(|| {
+		let phase_data = get_the_phase_data();
+		let now = 10;
+		for citizen_id in 0..60 {
+			let key = return_key_profile(citizen_id);
+			assert_ok!(TemplateModule::set_to_staking_period_pe(
+				key.clone(),
+				phase_data.clone(),
+				now
+			));
+		}
+		let staking_end = now + phase_data.staking_length;
+		assert_eq!(TemplateModule::period_change_queue(staking_end).len(), 50);
+		assert_eq!(TemplateModule::period_change_queue(staking_end + 1).len(), 10);
+		TemplateModule::on_initialize(staking_end);
+		assert_eq!(TemplateModule::period_change_queue(staking_end).len(), 0);
+		assert_eq!(TemplateModule::get_period(&return_key_profile(0)).unwrap(), Period::Drawing);
+		assert_eq!(TemplateModule::get_period(&return_key_profile(59)).unwrap(), Period::Staking);
+		TemplateModule::on_initialize(staking_end + 1);
+		assert_eq!(TemplateModule::get_period(&return_key_profile(59)).unwrap(), Period::Drawing);
+	});
+}
+
+#[test]
+fn game_events_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Evidence }.into(),
+		);
+		let phase_data = get_the_phase_data();
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+			System::assert_last_event(
+				Event::JurorStaked { key: key.clone(), who: j, stake: j * 100 }.into(),
+			);
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		System::assert_has_event(
+			Event::JurorDrawn { key: key.clone(), who: 12, stake: 1200 }.into(),
+		);
+		System::assert_has_event(
+			Event::JurorDrawn { key: key.clone(), who: 22, stake: 2200 }.into(),
+		);
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Commit }.into(),
+		);
+		assert_ok!(TemplateModule::unstaking_helper(key.clone(), 5));
+		System::assert_last_event(
+			Event::JurorUnstaked { key: key.clone(), who: 5, stake: 500 }.into(),
+		);
+		let votes = [
+			(12, 1, "salt"),
+			(15, 1, "salt2"),
+			(19, 1, "salt3"),
+			(20, 1, "salt4"),
+			(22, 0, "salt5"),
+		];
+		for (juror, choice, salt) in votes.iter() {
+			let hash = commit(&key, *juror, CommitChoice::TwoChoice(*choice), salt);
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), *juror, hash));
+			System::assert_last_event(
+				Event::VoteCommitted { key: key.clone(), who: *juror }.into(),
+			);
+		}
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		for (juror, choice, salt) in votes.iter() {
+			assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				*juror,
+				*choice,
+				salt.as_bytes().to_vec()
+			));
+			System::assert_last_event(
+				Event::VoteRevealed { key: key.clone(), who: *juror, choice: *choice }.into(),
+			);
+		}
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Appeal }.into(),
+		);
+		let new_now = new_now + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		System::assert_last_event(
+			Event::PeriodChanged { key: key.clone(), period: Period::Execution }.into(),
+		);
+		assert_ok!(TemplateModule::settle_two_choice_helper(
+			key.clone(),
+			phase_data.clone(),
+			u32::MAX
+		));
+		System::assert_has_event(
+			Event::GameDecided { key: key.clone(), decision: WinningDecision::WinnerYes }.into(),
+		);
+		System::assert_has_event(
+			Event::IncentivesPaid { key: key.clone(), who: 12, amount: 1225 }.into(),
+		);
+		System::assert_last_event(
+			Event::JurorSlashed { key: key.clone(), who: 22, amount: 550 }.into(),
+		);
+	});
+}
+
+#[test]
+fn multi_choice_schelling_game_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let phase_data = get_the_phase_data();
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_noop!(
+			TemplateModule::set_number_of_options_helper(key.clone(), 1),
+			Error::<Test>::InvalidNumberOfOptions
+		);
+		assert_ok!(TemplateModule::set_number_of_options_helper(key.clone(), 3));
+		assert_noop!(
+			TemplateModule::set_number_of_options_helper(key.clone(), 4),
+			Error::<Test>::NumberOfOptionsAlreadySet
+		);
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(drawn_jurors, vec![(12, 1200), (15, 1500), (19, 1900), (20, 2000), (22, 2200)]);
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let hash = commit(&key, 12, CommitChoice::MultiChoice(2), "salt");
+		assert_ok!(TemplateModule::commit_vote_multi_choice_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::MultiChoice(2), "salt2");
+		assert_ok!(TemplateModule::commit_vote_multi_choice_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::MultiChoice(2), "salt3");
+		assert_ok!(TemplateModule::commit_vote_multi_choice_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::MultiChoice(0), "salt4");
+		assert_ok!(TemplateModule::commit_vote_multi_choice_helper(key.clone(), 20, hash));
+		let hash = commit(&key, 22, CommitChoice::MultiChoice(1), "salt5");
+		assert_ok!(TemplateModule::commit_vote_multi_choice_helper(key.clone(), 22, hash));
+		assert_noop!(
+			TemplateModule::commit_vote_multi_choice_helper(key.clone(), 5, hash),
+			Error::<Test>::JurorDoesNotExists
+		);
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_noop!(
+			TemplateModule::reveal_vote_multi_choice_helper(
+				key.clone(),
+				12,
+				3,
+				"salt".as_bytes().to_vec()
+			),
+			Error::<Test>::NotValidChoice
+		);
+		assert_noop!(
+			TemplateModule::reveal_vote_multi_choice_helper(
+				key.clone(),
+				12,
+				1,
+				"salt".as_bytes().to_vec()
+			),
+			Error::<Test>::CommitDoesNotMatch
+		);
+		assert_ok!(TemplateModule::reveal_vote_multi_choice_helper(
+			key.clone(),
+			12,
+			2,
+			"salt".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_multi_choice_helper(
+			key.clone(),
+			15,
+			2,
+			"salt2".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_multi_choice_helper(
+			key.clone(),
+			19,
+			2,
+			"salt3".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_multi_choice_helper(
+			key.clone(),
+			20,
+			0,
+			"salt4".as_bytes().to_vec()
+		));
+		assert_eq!(TemplateModule::multi_choice_count(key.clone(), 2), 3);
+		assert_eq!(TemplateModule::multi_choice_count(key.clone(), 0), 1);
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_eq!(TemplateModule::get_winning_option_helper(key.clone()), Some(2));
+		assert_ok!(TemplateModule::settle_multi_choice_helper(
+			key.clone(),
+			phase_data.clone(),
+			u32::MAX
+		));
+		// 100 incentives shared by 3 winners
+		assert_eq!(300033, Balances::free_balance(12));
+		assert_eq!(300033, Balances::free_balance(15));
+		assert_eq!(300033, Balances::free_balance(19));
+		// Voted for other option, gets 3/4 of stake back
+		assert_eq!(299500, Balances::free_balance(20));
+		// Not revealed, stake slashed
+		assert_eq!(297800, Balances::free_balance(22));
+		assert_eq!(0, Balances::reserved_balance(22));
+	});
+}
+
+#[test]
+fn multi_choice_tie_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		assert_eq!(TemplateModule::get_winning_option(key.clone()), WinningOption::Draw);
+		crate::MultiChoiceCount::<Test>::insert(&key, 0, 2);
+		crate::MultiChoiceCount::<Test>::insert(&key, 3, 1);
+		assert_eq!(TemplateModule::get_winning_option(key.clone()), WinningOption::Winner(0));
+		crate::MultiChoiceCount::<Test>::insert(&key, 1, 2);
+		assert_eq!(TemplateModule::get_winning_option(key.clone()), WinningOption::Draw);
+		assert_eq!(TemplateModule::get_winning_option_helper(key.clone()), None);
+	});
+}
+
+#[test]
+fn non_participation_penalty_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let mut phase_data = get_the_phase_data();
+		phase_data.non_commit_penalty = Percent::from_percent(50);
+		phase_data.non_reveal_penalty = Percent::from_percent(100);
+		phase_data.max_commit_redraws = 0;
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(1), "salt2");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(1), "salt3");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(0), "salt4");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+		// Juror 22 does not commit
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			12,
+			1,
+			"salt".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			15,
+			1,
+			"salt2".as_bytes().to_vec()
+		));
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			19,
+			1,
+			"salt3".as_bytes().to_vec()
+		));
+		// Juror 20 does not reveal
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::settle_two_choice_helper(
+			key.clone(),
+			phase_data.clone(),
+			u32::MAX
+		));
+		assert_eq!(300033, Balances::free_balance(12));
+		// Whole stake of 2000 slashed
+		assert_eq!(298000, Balances::free_balance(20));
+		assert_eq!(0, Balances::reserved_balance(20));
+		System::assert_has_event(
+			Event::NonParticipationPenalty {
+				key: key.clone(),
+				who: 20,
+				reason: NonParticipation::NotRevealed,
+				amount: 2000,
+			}
+			.into(),
+		);
+		// Half of stake of 2200 slashed
+		assert_eq!(298900, Balances::free_balance(22));
+		assert_eq!(0, Balances::reserved_balance(22));
+		System::assert_has_event(
+			Event::NonParticipationPenalty {
+				key: key.clone(),
+				who: 22,
+				reason: NonParticipation::NotCommitted,
+				amount: 1100,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn score_game_without_revealed_score_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let mut phase_data = get_the_phase_data();
+		phase_data.non_commit_penalty = Percent::from_percent(50);
+		phase_data.max_commit_redraws = 0;
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		// Only juror 12 commits, and nobody reveals
+		let hash = commit(&key, 12, CommitChoice::Score(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), 12, hash));
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let vote_start_time = TemplateModule::vote_start_time(key.clone());
+		let new_now = vote_start_time + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		let appeal_start_time = TemplateModule::appeal_start_time(key.clone());
+		let new_now = appeal_start_time + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+
+		assert_eq!(
+			TemplateModule::settle_score_schelling_helper(
+				key.clone(),
+				phase_data.clone(),
+				RangePoint::ZeroToTen,
+				ScoreAggregation::MeanWithinStdDev,
+				u32::MAX
+			),
+			Ok((10, true))
+		);
+		let game_result = TemplateModule::game_result(key.clone()).unwrap();
+		assert_eq!(game_result.outcome, GameOutcome::Undecided);
+		assert_eq!(game_result.aggregated_score, None);
+		// Whole stake of 1200 slashed for not revealing
+		assert_eq!(298800, Balances::free_balance(12));
+		assert_eq!(0, Balances::reserved_balance(12));
+		// Half of stake of 2200 slashed for not committing
+		assert_eq!(298900, Balances::free_balance(22));
+		assert_eq!(0, Balances::reserved_balance(22));
+	});
+}
+
+#[test]
+fn redraw_jurors_without_commit_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		let now = 10;
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let phase_data = get_the_phase_data();
+		let staking_start_time = now + phase_data.evidence_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		for juror in [12, 15, 19] {
+			let hash = commit(&key, juror, CommitChoice::TwoChoice(1), &format!("salt{}", juror));
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), juror, hash));
+		}
+		// Jurors 20 and 22 do not commit, they are replaced
+		let commit_start_time = TemplateModule::commit_start_time(key.clone());
+		let new_now = commit_start_time + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Drawing);
+		assert_eq!(1, TemplateModule::commit_redraws(key.clone()));
+		assert_eq!(TemplateModule::replaced_jurors(key.clone()), vec![(20, 2000), (22, 2200)]);
+		assert_eq!(
+			TemplateModule::drawn_jurors(key.clone()),
+			vec![(12, 1200), (15, 1500), (19, 1900)]
+		);
+		assert_eq!(3, TemplateModule::draws_in_round(key.clone()));
+		assert_noop!(
+			TemplateModule::apply_jurors_helper(key.clone(), phase_data.clone(), 20, 2000),
+			Error::<Test>::PeriodDontMatch
+		);
+
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(5, drawn_jurors.len());
+		assert!(!drawn_jurors.iter().any(|(juror, _)| *juror == 20 || *juror == 22));
+		let redraw_now = new_now + 1;
+		assert_ok!(TemplateModule::change_period(
+			key.clone(),
+			phase_data.clone(),
+			redraw_now.clone()
+		));
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Commit);
+		assert_eq!(redraw_now, TemplateModule::commit_start_time(key.clone()));
+
+		// One of the replacement jurors does not commit, redraws are exhausted
+		let (last_juror, _) = drawn_jurors
+			.iter()
+			.find(|(juror, _)| ![12, 15, 19].contains(juror))
+			.unwrap()
+			.clone();
+		for (juror, _) in drawn_jurors.iter() {
+			if ![12, 15, 19, last_juror].contains(juror) {
+				let hash =
+					commit(&key, *juror, CommitChoice::TwoChoice(1), &format!("salt{}", juror));
+				assert_ok!(TemplateModule::commit_vote_helper(key.clone(), *juror, hash));
+			}
+		}
+		let new_now = redraw_now + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now.clone()));
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Vote);
+		assert_eq!(5, TemplateModule::drawn_jurors(key.clone()).len());
+	});
+}
+
+#[test]
+fn draw_distinct_jurors_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_staking_period_pe(key.clone(), phase_data.clone(), 10));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		// Juror 4 holds almost the whole stake, but is drawn only once
+		assert_ok!(TemplateModule::apply_jurors_helper(key.clone(), phase_data.clone(), 4, 250000));
+		for j in 5..10 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				100
+			));
+		}
+		let new_now = 10 + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		// One seed for all the draws of the drawing period
+		assert_eq!(crate::Nonce::<Test>::get(), 1);
+		assert!(TemplateModule::draw_seed(key.clone()).is_some());
+		assert_eq!(5, TemplateModule::draws_in_round(key.clone()));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(drawn_jurors, vec![(4, 250000), (5, 100), (7, 100), (8, 100), (9, 100)]);
+		// Drawn jurors are removed from the tree, so later calls do not draw them again
+		for (juror, _) in drawn_jurors.iter() {
+			assert_eq!(SortitionSumGame::stake_of(key.clone(), *juror), Ok(None));
+		}
+		assert_eq!(SortitionSumGame::stake_of(key.clone(), 6), Ok(Some(100)));
+	});
+}
+
+struct CurrentBlockRandomness;
+
+impl frame_support::traits::Randomness<sp_core::H256, u64> for CurrentBlockRandomness {
+	fn random(_subject: &[u8]) -> (sp_core::H256, u64) {
+		(sp_core::H256::repeat_byte(1), System::block_number())
+	}
+}
+
+struct CurrentBlockVrfRandomness;
+
+impl frame_support::traits::Randomness<Option<sp_core::H256>, u64> for CurrentBlockVrfRandomness {
+	fn random(_subject: &[u8]) -> (Option<sp_core::H256>, u64) {
+		(Some(sp_core::H256::repeat_byte(1)), System::block_number())
+	}
+}
+
+#[test]
+fn future_block_randomness_test() {
+	new_test_ext().execute_with(|| {
+		type Provider = FutureBlockRandomness<CurrentBlockRandomness, ConstU64<3>>;
+		System::set_block_number(10);
+		let seed_block = Provider::seed_block(10);
+		assert_eq!(seed_block, 13);
+		assert_eq!(Provider::random(b"draw", seed_block), None);
+		System::set_block_number(12);
+		assert_eq!(Provider::random(b"draw", seed_block), None);
+		System::set_block_number(13);
+		assert_eq!(Provider::random(b"draw", seed_block), Some(sp_core::H256::repeat_byte(1)));
+	});
+}
+
+struct LaggingRandomness;
+
+impl frame_support::traits::Randomness<sp_core::H256, u64> for LaggingRandomness {
+	fn random(_subject: &[u8]) -> (sp_core::H256, u64) {
+		(sp_core::H256::repeat_byte(1), System::block_number().saturating_sub(81))
+	}
+}
+
+#[test]
+fn lagging_randomness_availability_delay_test() {
+	new_test_ext().execute_with(|| {
+		type Provider = FutureBlockRandomness<LaggingRandomness, ConstU64<1>, ConstU64<81>>;
+		assert_eq!(<Provider as DrawRandomness<sp_core::H256, u64>>::availability_delay(), 82);
+		let seed_block = <Provider as DrawRandomness<sp_core::H256, u64>>::seed_block(10);
+		System::set_block_number(10 + 81);
+		assert_eq!(Provider::random(b"draw", seed_block), None);
+		System::set_block_number(10 + 82);
+		assert_eq!(Provider::random(b"draw", seed_block), Some(sp_core::H256::repeat_byte(1)));
+	});
+}
+
+#[test]
+fn vrf_randomness_test() {
+	new_test_ext().execute_with(|| {
+		type Provider = VrfRandomness<CurrentBlockVrfRandomness, ConstU64<2>>;
+		System::set_block_number(10);
+		let seed_block = Provider::seed_block(10);
+		assert_eq!(Provider::random(b"draw", seed_block), None);
+		System::set_block_number(12);
+		assert_eq!(Provider::random(b"draw", seed_block), Some(sp_core::H256::repeat_byte(1)));
+	});
+}
+
+#[test]
+fn score_aggregation_test() {
+	new_test_ext().execute_with(|| {
+		// Scores of score_schelling_game_test with stakes of the drawn jurors
+		let scores = vec![(1000, 400), (1000, 700), (5000, 1300), (1000, 1400), (7000, 1500)];
+		assert_eq!(
+			TemplateModule::aggregate_score(&ScoreAggregation::MeanWithinStdDev, &scores),
+			Some(2000)
+		);
+		assert_eq!(TemplateModule::aggregate_score(&ScoreAggregation::Median, &scores), Some(1000));
+		assert_eq!(
+			TemplateModule::aggregate_score(&ScoreAggregation::InterquartileMean, &scores),
+			Some(2333)
+		);
+		assert_eq!(
+			TemplateModule::aggregate_score(&ScoreAggregation::StakeWeightedMean, &scores),
+			Some(3679)
+		);
+
+		// Even count of scores
+		let scores = vec![(2000, 100), (2000, 100), (8000, 100), (9000, 100)];
+		assert_eq!(
+			TemplateModule::aggregate_score(&ScoreAggregation::MeanWithinStdDev, &scores),
+			Some(4000)
+		);
+		assert_eq!(TemplateModule::aggregate_score(&ScoreAggregation::Median, &scores), Some(5000));
+		assert_eq!(
+			TemplateModule::aggregate_score(&ScoreAggregation::InterquartileMean, &scores),
+			Some(5000)
+		);
+
+		for aggregation in [
+			ScoreAggregation::MeanWithinStdDev,
+			ScoreAggregation::Median,
+			ScoreAggregation::InterquartileMean,
+			ScoreAggregation::StakeWeightedMean,
+		] {
+			assert_eq!(TemplateModule::aggregate_score(&aggregation, &vec![]), None);
+		}
+	});
+}
+
+#[test]
+fn stake_weighted_score_rewards_test() {
+	new_test_ext().execute_with(|| {
+		let key = return_key_profile(0);
+		let now = 10;
+		let mut phase_data = get_the_phase_data();
+		phase_data.reward_curve = RewardCurve {
+			loser_slash: Percent::from_percent(25),
+			max_distance: 6000,
+			stake_weighted: true,
+		};
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let staking_start_time = now + phase_data.staking_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let votes = [(12, 1), (15, 1), (19, 5), (20, 1), (22, 7)];
+		for (juror, score) in votes {
+			let hash = commit(&key, juror, CommitChoice::Score(score), "salt");
+			assert_ok!(TemplateModule::commit_vote_for_score_helper(key.clone(), juror, hash));
+		}
+		let new_now = TemplateModule::commit_start_time(key.clone()) + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		for (juror, score) in votes {
+			assert_ok!(TemplateModule::reveal_vote_score_helper(
+				key.clone(),
+				juror,
+				score,
+				"salt".as_bytes().to_vec()
+			));
+		}
+		let new_now = TemplateModule::vote_start_time(key.clone()) + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let new_now = TemplateModule::appeal_start_time(key.clone()) + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_eq!(TemplateModule::get_period(key.clone()), Some(Period::Execution));
+
+		let total_issuance = Balances::total_issuance();
+		assert_ok!(TemplateModule::settle_score_schelling_helper(
+			key.clone(),
+			phase_data.clone(),
+			RangePoint::ZeroToTen,
+			ScoreAggregation::MeanWithinStdDev,
+			u32::MAX
+		));
+		assert_eq!(TemplateModule::new_mean_reveal_score(key.clone()), 2000);
+		// Losers are slashed by distance from the mean, 475 * 3000 / 6000 and 550 * 5000 / 6000
+		assert_eq!(Balances::free_balance(19), 300000 - 237);
+		assert_eq!(Balances::free_balance(22), 300000 - 458);
+		// Winners share 100 and the slashed 695 pro rata to stakes 1200, 1500 and 2000
+		assert_eq!(Balances::free_balance(12), 300000 + 202);
+		assert_eq!(Balances::free_balance(15), 300000 + 253);
+		assert_eq!(Balances::free_balance(20), 300000 + 338);
+		assert_eq!(Balances::total_issuance(), total_issuance + 793 - 695);
+		let game_result = TemplateModule::get_result_link(key.clone()).unwrap();
+		assert_eq!(game_result.outcome, GameOutcome::Score);
+		assert_eq!(game_result.aggregated_score, Some(2000));
+	});
+}
+
+#[test]
+fn reward_curve_slash_amount_test() {
+	new_test_ext().execute_with(|| {
+		let mut phase_data = get_the_phase_data();
+		// Default curve slashes one fourth of the stake
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 1300, None), 325);
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 1300, Some(3000)), 325);
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 5, None), 2);
+		phase_data.reward_curve.max_distance = 6000;
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 1300, Some(-3000)), 162);
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 1300, Some(9000)), 325);
+		assert_eq!(TemplateModule::loser_slash_amount(&phase_data, 1300, None), 325);
+	});
+}
+
+#[test]
+fn paginated_settlement_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let staking_start_time = now + phase_data.staking_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let votes = [(12, 1), (15, 1), (19, 1), (20, 1), (22, 0)];
+		for (juror, choice) in votes {
+			let hash = commit(&key, juror, CommitChoice::TwoChoice(choice), "salt");
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), juror, hash));
+		}
+		let new_now = TemplateModule::commit_start_time(key.clone()) + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		for (juror, choice) in votes {
+			assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				juror,
+				choice,
+				"salt".as_bytes().to_vec()
+			));
+		}
+		let new_now = TemplateModule::vote_start_time(key.clone()) + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let new_now = TemplateModule::appeal_start_time(key.clone()) + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_eq!(TemplateModule::get_period(key.clone()), Some(Period::Execution));
+
+		// First page only tallies the jurors
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 3),
+			Ok((3, false))
+		);
+		System::assert_has_event(
+			Event::GameDecided { key: key.clone(), decision: WinningDecision::WinnerYes }.into(),
+		);
+		assert_eq!(TemplateModule::game_settlement(key.clone()).tallied, 3);
+		assert_eq!(Balances::free_balance(12), 298800);
+
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 3),
+			Ok((3, false))
+		);
+		assert_eq!(Balances::free_balance(12), 300025);
+		assert_eq!(Balances::free_balance(15), 298500);
+
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 3),
+			Ok((3, false))
+		);
+		assert_eq!(Balances::free_balance(15), 300025);
+		assert_eq!(Balances::free_balance(20), 300025);
+		assert_eq!(Balances::free_balance(22), 297800);
+		assert_eq!(TemplateModule::drawn_jurors(key.clone()).len(), 5);
+
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), 3),
+			Ok((1, true))
+		);
+		assert_eq!(Balances::free_balance(22), 299450);
+		System::assert_last_event(Event::GameSettled { key: key.clone() }.into());
+		assert_eq!(TemplateModule::drawn_jurors(key.clone()), vec![]);
+		assert_eq!(TemplateModule::game_settlement(key.clone()), Default::default());
+
+		// Jurors who were not drawn are refunded after settlement
+		assert_eq!(Balances::reserved_balance(4), 400);
+		assert_ok!(TemplateModule::refund_stakes(RuntimeOrigin::signed(1), key.clone(), 10));
+		assert_eq!(SortitionSumGame::stakers_count(key.clone()), Ok(11));
+		assert_ok!(TemplateModule::refund_stakes(RuntimeOrigin::signed(1), key.clone(), 20));
+		for j in 4..30 {
+			assert_eq!(Balances::reserved_balance(j), 0);
+		}
+		assert_eq!(SortitionSumGame::sortition_sum_trees(key.clone()), None);
+	});
+}
+
+#[test]
+fn game_result_and_pruning_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let staking_start_time = now + phase_data.staking_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let votes = [(12, 1), (15, 1), (19, 1), (20, 1), (22, 0)];
+		for (juror, choice) in votes {
+			let hash = commit(&key, juror, CommitChoice::TwoChoice(choice), "salt");
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), juror, hash));
+		}
+		let new_now = TemplateModule::commit_start_time(key.clone()) + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		for (juror, choice) in votes {
+			assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				juror,
+				choice,
+				"salt".as_bytes().to_vec()
+			));
+		}
+		let new_now = TemplateModule::vote_start_time(key.clone()) + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let new_now = TemplateModule::appeal_start_time(key.clone()) + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let started_at = TemplateModule::evidence_start_time(key.clone());
+
+		assert_eq!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), u32::MAX),
+			Ok((10, true))
+		);
+		let game_result = GameResult {
+			outcome: GameOutcome::TwoChoice(WinningDecision::WinnerYes),
+			aggregated_score: None,
+			decided_at: 1,
+			rounds: 1,
+			juror_count: 5,
+			started_at,
+			settled_at: Some(1),
+		};
+		assert_eq!(TemplateModule::get_result_link(key.clone()), Some(game_result.clone()));
+		assert_eq!(TemplateModule::game_result(key.clone()), Some(game_result.clone()));
+		assert_eq!(TemplateModule::prune_queue(101), vec![key.clone()]);
+		assert_noop!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), u32::MAX),
+			Error::<Test>::GameAlreadySettled
+		);
+
+		// Game storage is kept until the end of the retention period
+		TemplateModule::on_initialize(100);
+		assert_eq!(TemplateModule::get_period(key.clone()), Some(Period::Execution));
+		assert_eq!(TemplateModule::decision_count(key.clone()), (1, 4));
+
+		TemplateModule::on_initialize(101);
+		assert_eq!(TemplateModule::get_period(key.clone()), None);
+		assert_eq!(TemplateModule::decision_count(key.clone()), (0, 0));
+		assert_eq!(TemplateModule::evidence_start_time(key.clone()), 0);
+		assert_eq!(TemplateModule::prune_queue(101), vec![]);
+		assert_eq!(TemplateModule::game_result(key.clone()), Some(game_result));
+	});
+}
+
+#[test]
+fn prune_game_storage_in_pages_test() {
+	let mut ext = new_test_ext();
+	let key = return_key_profile(0);
+	ext.execute_with(|| {
+		PeriodName::<Test>::insert(&key, Period::Execution);
+		for j in 0..250 {
+			UnstakedJurors::<Test>::insert(&key, j, ());
+		}
+		assert_ok!(PruneQueue::<Test>::try_append(5, &key));
+	});
+	// Entries removed in a block are removed from the backend when the block is committed
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		// More entries than `max_pruned_entries`, pruning continues in the next block
+		TemplateModule::on_initialize(5);
+		assert_eq!(TemplateModule::prune_queue(6), vec![key.clone()]);
+		assert_eq!(TemplateModule::get_period(key.clone()), Some(Period::Execution));
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		TemplateModule::on_initialize(6);
+		assert_eq!(TemplateModule::prune_queue(7), vec![]);
+		assert_eq!(TemplateModule::get_period(key.clone()), None);
+		assert_eq!(UnstakedJurors::<Test>::iter_prefix(&key).count(), 0);
+	});
+}
+
+#[test]
+fn commit_hash_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let salt = "salt".as_bytes();
+		let choice = CommitChoice::TwoChoice(1);
+		let preimage = (COMMIT_DOMAIN, COMMIT_VERSION, key.clone(), 4u64, choice.clone(), salt);
+		assert_eq!(
+			TemplateModule::commit_hash(&key, &4, &choice, salt),
+			sp_io::hashing::keccak_256(&preimage.encode())
+		);
+		assert_ne!(
+			TemplateModule::commit_hash(&key, &4, &choice, salt),
+			TemplateModule::commit_hash(&key, &7, &choice, salt)
+		);
+		assert_ne!(
+			TemplateModule::commit_hash(&key, &4, &choice, salt),
+			TemplateModule::commit_hash(&return_key_profile(1), &4, &choice, salt)
+		);
+		assert_ne!(
+			TemplateModule::commit_hash(&key, &4, &CommitChoice::TwoChoice(1), salt),
+			TemplateModule::commit_hash(&key, &4, &CommitChoice::MultiChoice(1), salt)
+		);
+
+		let now = 10;
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let staking_start_time = now + phase_data.staking_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+
+		// Commit built with the account of another juror
+		let hash = commit(&key, 15, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+		// Commit built with the key of another game
+		let hash = commit(&return_key_profile(1), 15, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 15, hash));
+		// Salt shorter than `MinSaltLength`
+		let hash = commit(&key, 19, CommitChoice::TwoChoice(1), "abc");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 19, hash));
+		let hash = commit(&key, 20, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 20, hash));
+
+		let new_now = TemplateModule::commit_start_time(key.clone()) + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_noop!(
+			TemplateModule::reveal_vote_two_choice_helper(key.clone(), 12, 1, salt.to_vec()),
+			Error::<Test>::CommitDoesNotMatch
+		);
+		assert_noop!(
+			TemplateModule::reveal_vote_two_choice_helper(key.clone(), 15, 1, salt.to_vec()),
+			Error::<Test>::CommitDoesNotMatch
+		);
+		assert_noop!(
+			TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				19,
+				1,
+				"abc".as_bytes().to_vec()
+			),
+			Error::<Test>::SaltTooShort
+		);
+		assert_noop!(
+			TemplateModule::reveal_vote_two_choice_helper(key.clone(), 20, 0, salt.to_vec()),
+			Error::<Test>::CommitDoesNotMatch
+		);
+		assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+			key.clone(),
+			20,
+			1,
+			salt.to_vec()
+		));
+	});
+}
+
+#[test]
+fn juror_stats_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let now = 10;
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), now));
+		let staking_start_time = now + phase_data.staking_length;
+		assert_ok!(TemplateModule::set_to_staking_period(
+			key.clone(),
+			phase_data.clone(),
+			staking_start_time
+		));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = staking_start_time + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let votes = [(12, 1), (15, 1), (19, 1), (20, 1), (22, 0)];
+		for (juror, choice) in votes {
+			let hash = commit(&key, juror, CommitChoice::TwoChoice(choice), "salt");
+			assert_ok!(TemplateModule::commit_vote_helper(key.clone(), juror, hash));
+		}
+		let new_now = TemplateModule::commit_start_time(key.clone()) + phase_data.commit_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		// Juror 20 does not reveal
+		for (juror, choice) in [(12, 1), (15, 1), (19, 1), (22, 0)] {
+			assert_ok!(TemplateModule::reveal_vote_two_choice_helper(
+				key.clone(),
+				juror,
+				choice,
+				"salt".as_bytes().to_vec()
+			));
+		}
+		let new_now = TemplateModule::vote_start_time(key.clone()) + phase_data.vote_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let new_now = TemplateModule::appeal_start_time(key.clone()) + phase_data.appeal_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_eq!(TemplateModule::juror_stats(12), JurorStats::default());
+
+		assert_ok!(TemplateModule::settle_two_choice_helper(
+			key.clone(),
+			phase_data.clone(),
+			u32::MAX
+		));
+		let winner_stats = JurorStats {
+			games_drawn: 1,
+			committed: 1,
+			revealed: 1,
+			coherent: 1,
+			slashed: 0,
+			rewards: 33,
+		};
+		assert_eq!(TemplateModule::juror_stats(12), winner_stats);
+		assert_eq!(TemplateModule::juror_stats(19), winner_stats);
+		assert_eq!(
+			TemplateModule::juror_stats(20),
+			JurorStats {
+				games_drawn: 1,
+				committed: 1,
+				revealed: 0,
+				coherent: 0,
+				slashed: 2000,
+				rewards: 0,
+			}
+		);
+		assert_eq!(
+			TemplateModule::juror_stats(22),
+			JurorStats {
+				games_drawn: 1,
+				committed: 1,
+				revealed: 1,
+				coherent: 0,
+				slashed: 550,
+				rewards: 0,
+			}
+		);
+		assert_eq!(TemplateModule::juror_stats(5), JurorStats::default());
+		assert_eq!(TemplateModule::juror_coherence(&12), Percent::from_percent(100));
+		assert_eq!(TemplateModule::juror_coherence(&22), Percent::from_percent(0));
+		assert_eq!(TemplateModule::juror_coherence(&20), Percent::from_percent(0));
+	});
+}
+
+#[test]
+fn set_phase_data_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let game_type = SchellingGameType::ProfileApproval;
+		let phase_data = PhaseData::<Test>::create_with_data(
+			PhaseLengths::uniform(100),
+			7,
+			10,
+			500,
+			(100, 200),
+			2,
+		);
+		assert_eq!(TemplateModule::get_phase_data_link(game_type.clone()), None);
+		assert_noop!(
+			TemplateModule::set_phase_data(
+				RuntimeOrigin::signed(1),
+				game_type.clone(),
+				phase_data.clone()
+			),
+			DispatchError::BadOrigin
+		);
+
+		let mut invalid_phase_data = phase_data.clone();
+		invalid_phase_data.commit_length = 0;
+		assert_noop!(
+			TemplateModule::set_phase_data(
+				RuntimeOrigin::root(),
+				game_type.clone(),
+				invalid_phase_data
+			),
+			Error::<Test>::InvalidPhaseData
+		);
+		let mut invalid_phase_data = phase_data.clone();
+		invalid_phase_data.max_draws = 0;
+		assert_noop!(
+			TemplateModule::set_phase_data(
+				RuntimeOrigin::root(),
+				game_type.clone(),
+				invalid_phase_data
+			),
+			Error::<Test>::InvalidPhaseData
+		);
+		// 12 jurors in the first round are 51 jurors after two appeals, more than `MaxDrawsPerRound`
+		let mut invalid_phase_data = phase_data.clone();
+		invalid_phase_data.max_draws = 12;
+		assert_noop!(
+			TemplateModule::set_phase_data(
+				RuntimeOrigin::root(),
+				game_type.clone(),
+				invalid_phase_data
+			),
+			Error::<Test>::InvalidPhaseData
+		);
+
+		assert_ok!(TemplateModule::set_phase_data(
+			RuntimeOrigin::root(),
+			game_type.clone(),
+			phase_data.clone()
+		));
+		System::assert_last_event(Event::PhaseDataSet { game_type: game_type.clone() }.into());
+		assert_eq!(
+			TemplateModule::game_type_phase_data(game_type.clone()),
+			Some(phase_data.clone())
+		);
+		assert_eq!(TemplateModule::get_phase_data_link(game_type), Some(phase_data));
+		assert_eq!(
+			TemplateModule::get_phase_data_link(SchellingGameType::PositiveExternality),
+			None
+		);
+	});
+}
+
+#[test]
+fn create_phase_data_test() {
+	new_test_ext().execute_with(|| {
+		let phase_lengths = PhaseLengths {
+			evidence: 1000,
+			staking: 500,
+			drawing: 100,
+			commit: 200,
+			vote: 20,
+			appeal: 300,
+		};
+		let phase_data =
+			TemplateModule::create_phase_data(phase_lengths.clone(), 5, 3, 100, (100, 100), 2)
+				.unwrap();
+		assert_eq!(phase_data.evidence_length, 1000);
+		assert_eq!(phase_data.staking_length, 500);
+		assert_eq!(phase_data.drawing_length, 100);
+		assert_eq!(phase_data.commit_length, 200);
+		assert_eq!(phase_data.vote_length, 20);
+		assert_eq!(phase_data.appeal_length, 300);
+		assert_eq!(phase_data.appeal_fee, 500);
+		assert_eq!(phase_data.max_appeals, 2);
+
+		let invalid_phase_lengths = PhaseLengths { vote: 0, ..phase_lengths.clone() };
+		assert_noop!(
+			TemplateModule::create_phase_data(invalid_phase_lengths, 5, 3, 100, (100, 100), 2),
+			Error::<Test>::InvalidPhaseData
+		);
+		assert_noop!(
+			TemplateModule::create_phase_data(phase_lengths.clone(), 5, 3, 0, (100, 100), 2),
+			Error::<Test>::InvalidPhaseData
+		);
+		// 5, 11, 23, 47, 95 jurors, last round draws more than `MaxDrawsPerRound`
+		assert_ok!(TemplateModule::create_phase_data(
+			phase_lengths.clone(),
+			5,
+			3,
+			100,
+			(100, 100),
+			3
+		));
+		assert_noop!(
+			TemplateModule::create_phase_data(phase_lengths, 5, 3, 100, (100, 100), 4),
+			Error::<Test>::InvalidPhaseData
+		);
+	});
+}
+
+#[test]
+fn drawing_timeout_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let phase_data = get_the_phase_data();
+		assert_eq!(phase_data.min_draws, 3);
+		let start_drawing = |key: SumTreeName<u64, u64>| {
+			let staking_start_time = 10;
+			assert_ok!(TemplateModule::set_to_staking_period_pe(
+				key.clone(),
+				phase_data.clone(),
+				staking_start_time
+			));
+			assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+			for j in 4..30 {
+				assert_ok!(TemplateModule::apply_jurors_helper(
+					key.clone(),
+					phase_data.clone(),
+					j,
+					j * 100
+				));
+			}
+			let drawing_start_time = staking_start_time + phase_data.staking_length;
+			assert_ok!(TemplateModule::change_period(
+				key.clone(),
+				phase_data.clone(),
+				drawing_start_time
+			));
+			assert_eq!(TemplateModule::drawing_start_time(key.clone()), drawing_start_time);
+			drawing_start_time
+		};
+
+		// Drawing times out with more than `min_draws` jurors, game continues with them
+		let key = return_key_profile(0);
+		let drawing_start_time = start_drawing(key.clone());
+		let drawing_end = drawing_start_time + phase_data.drawing_length;
+		assert_eq!(TemplateModule::period_change_queue(drawing_end), vec![key.clone()]);
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 3));
+		assert_noop!(
+			TemplateModule::change_period(key.clone(), phase_data.clone(), drawing_end - 1),
+			Error::<Test>::MaxJurorNotDrawn
+		);
+		TemplateModule::on_initialize(drawing_end);
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Commit);
+		assert_eq!(TemplateModule::drawn_jurors(key.clone()).len(), 3);
+
+		// Drawing times out with less than `min_draws` jurors, game is cancelled
+		let key = return_key_profile(1);
+		let drawing_start_time = start_drawing(key.clone());
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 2));
+		let drawn_jurors = TemplateModule::drawn_jurors(key.clone());
+		assert_eq!(drawn_jurors.len(), 2);
+		for (juror, stake) in drawn_jurors.iter() {
+			assert_eq!(Balances::reserved_balance(juror), *stake);
+		}
+		let drawing_end = drawing_start_time + phase_data.drawing_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), drawing_end));
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Execution);
+		System::assert_last_event(Event::GameCancelled { key: key.clone() }.into());
+		for (juror, _) in drawn_jurors.iter() {
+			assert_eq!(Balances::reserved_balance(juror), 0);
+			assert_eq!(Balances::free_balance(juror), 300000);
+		}
+		assert!(TemplateModule::drawn_jurors(key.clone()).is_empty());
+		let game_result = TemplateModule::game_result(key.clone()).unwrap();
+		assert_eq!(game_result.outcome, GameOutcome::Undecided);
+		assert_eq!(game_result.settled_at, Some(1));
+
+		// Stakes of jurors who were not drawn are also refunded
+		for j in 4..30 {
+			assert_eq!(Balances::reserved_balance(j), 0);
+		}
+		assert_noop!(
+			TemplateModule::settle_two_choice_helper(key.clone(), phase_data.clone(), u32::MAX),
+			Error::<Test>::GameAlreadySettled
+		);
+
+		TemplateModule::on_initialize(101);
+		assert_eq!(TemplateModule::get_period(key.clone()), None);
+		assert_eq!(TemplateModule::drawing_start_time(key.clone()), 0);
+	});
+}
+
+#[test]
+fn cancel_game_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let phase_data = get_the_phase_data();
+		assert_noop!(
+			TemplateModule::cancel_game(RuntimeOrigin::root(), key.clone()),
+			Error::<Test>::PeriodDoesNotExists
+		);
+		assert_ok!(TemplateModule::set_to_staking_period_pe(key.clone(), phase_data.clone(), 10));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		let new_now = 10 + phase_data.staking_length;
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		assert_ok!(TemplateModule::draw_jurors_helper(key.clone(), phase_data.clone(), 5));
+		assert_ok!(TemplateModule::change_period(key.clone(), phase_data.clone(), new_now));
+		let hash = commit(&key, 12, CommitChoice::TwoChoice(1), "salt");
+		assert_ok!(TemplateModule::commit_vote_helper(key.clone(), 12, hash));
+
+		assert_noop!(
+			TemplateModule::cancel_game(RuntimeOrigin::signed(1), key.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::cancel_game(RuntimeOrigin::root(), key.clone()));
+		System::assert_last_event(Event::GameCancelled { key: key.clone() }.into());
+		assert_eq!(TemplateModule::get_period(&key).unwrap(), Period::Execution);
+		for j in 4..30 {
+			assert_eq!(Balances::reserved_balance(j), 0);
+			assert_eq!(Balances::free_balance(j), 300000);
+		}
+		assert!(TemplateModule::drawn_jurors(key.clone()).is_empty());
+		assert!(TemplateModule::vote_commits(key.clone(), 12).is_some());
+		assert_eq!(TemplateModule::game_phase_data(key.clone()), Some(phase_data.clone()));
+		let game_result = TemplateModule::get_result_link(key.clone()).unwrap();
+		assert_eq!(game_result.outcome, GameOutcome::Cancelled);
+		assert_eq!(game_result.juror_count, 5);
+		assert_eq!(game_result.settled_at, Some(1));
+
+		assert_noop!(
+			TemplateModule::cancel_game_link(key.clone()),
+			Error::<Test>::GameAlreadyConcluded
+		);
+		assert_noop!(
+			TemplateModule::apply_jurors_helper(key.clone(), phase_data.clone(), 3, 300),
+			Error::<Test>::PeriodDontMatch
+		);
+
+		// Commits are removed when the game storage is pruned
+		TemplateModule::on_initialize(101);
+		assert!(TemplateModule::vote_commits(key.clone(), 12).is_none());
+		assert_eq!(TemplateModule::get_period(&key), None);
+	});
+}
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		let key = return_key_profile(0);
+		v1::UnstakedJurors::<Test>::insert(&key, vec![4, 7]);
+		v1::JurorsIncentiveDistributedAccounts::<Test>::insert(&key, vec![13]);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert!(UnstakedJurors::<Test>::contains_key(&key, 4));
+		assert!(UnstakedJurors::<Test>::contains_key(&key, 7));
+		assert!(!UnstakedJurors::<Test>::contains_key(&key, 13));
+		assert!(JurorsIncentiveDistributedAccounts::<Test>::contains_key(&key, 13));
+	});
+}
+
+#[test]
+fn game_concluded_hook_failure_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(FAILING_CITIZEN);
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), 1));
+		assert_ok!(TemplateModule::cancel_game_link(key.clone()));
+		System::assert_has_event(
+			Event::GameConcludedHookFailed { key: key.clone(), error: "hook failed".into() }.into(),
+		);
+		System::assert_last_event(Event::GameCancelled { key: key.clone() }.into());
+		assert_eq!(unhashed::get::<SumTreeName<u64, u64>>(CONCLUDED_KEY), None);
+		let game_result = TemplateModule::get_result_link(key.clone()).unwrap();
+		assert_eq!(game_result.outcome, GameOutcome::Cancelled);
+
+		let key = return_key_profile(0);
+		assert_ok!(TemplateModule::set_to_evidence_period(key.clone(), get_the_phase_data(), 1));
+		assert_ok!(TemplateModule::cancel_game_link(key.clone()));
+		assert_eq!(unhashed::get::<SumTreeName<u64, u64>>(CONCLUDED_KEY), Some(key));
+	});
+}
+
+#[test]
+fn cancel_game_refunds_in_pages_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = return_key_profile(0);
+		let phase_data = get_the_phase_data();
+		assert_ok!(TemplateModule::set_to_staking_period_pe(key.clone(), phase_data.clone(), 10));
+		assert_ok!(TemplateModule::create_tree_link_helper(key.clone(), 3));
+		for j in 4..30 {
+			assert_ok!(TemplateModule::apply_jurors_helper(
+				key.clone(),
+				phase_data.clone(),
+				j,
+				j * 100
+			));
+		}
+		assert_noop!(
+			TemplateModule::refund_stakes(RuntimeOrigin::signed(1), key.clone(), 10),
+			Error::<Test>::GameNotSettled
+		);
+
+		assert_eq!(TemplateModule::refund_stakers(key.clone(), 10), 10);
+		assert_eq!(SortitionSumGame::stakers_count(key.clone()), Ok(16));
+		assert_eq!((4..30).filter(|j| Balances::reserved_balance(*j) == 0).count(), 10);
+
+		let post_info = TemplateModule::cancel_game(RuntimeOrigin::root(), key.clone()).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::cancel_game(16)));
+		for j in 4..30 {
+			assert_eq!(Balances::reserved_balance(j), 0);
+			assert_eq!(Balances::free_balance(j), 300000);
+		}
+		assert_eq!(SortitionSumGame::sortition_sum_trees(key.clone()), None);
+		assert_ok!(TemplateModule::refund_stakes(RuntimeOrigin::signed(1), key.clone(), 10));
 	});
 }
@@ -26,9 +26,21 @@ pub enum SchellingGameType {
 	DepartmentScore,
 }
 
+/// Length of each period of a schelling game in blocks
+#[derive(PartialEq, Eq, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PhaseLengths {
+	pub evidence: u64,
+	pub staking: u64,
+	pub drawing: u64,
+	pub commit: u64,
+	pub vote: u64,
+	pub appeal: u64,
+}
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
 pub struct PhaseData<T: Config> {
 	pub evidence_length: T::BlockNumber,
 	pub staking_length: T::BlockNumber,
@@ -37,9 +49,65 @@ pub struct PhaseData<T: Config> {
 	pub vote_length: T::BlockNumber,
 	pub appeal_length: T::BlockNumber,
 	pub max_draws: u64,
+	pub min_draws: u64, // Jurors needed to continue when drawing period ends, game is cancelled with fewer
 	pub min_number_juror_staked: u64,
 	pub min_juror_stake: BalanceOf<T>,
 	pub juror_incentives: (u64, u64), // (looser burn, winner mint)
+	pub max_appeals: u32,
+	pub appeal_fee: BalanceOf<T>,
+	pub non_commit_penalty: Percent, // Part of stake slashed if drawn juror does not commit
+	pub non_reveal_penalty: Percent, // Part of stake slashed if drawn juror does not reveal
+	pub max_commit_redraws: u32, // Times jurors who did not commit are replaced in a round
+	pub reward_curve: RewardCurve,
+}
+
+/// Parameters of rewards and slashing of jurors after the decision
+#[derive(PartialEq, Eq, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RewardCurve {
+	/// Part of the stake slashed from a juror who voted against the decision
+	/// In score schelling game it is the slash at `max_distance` from the aggregated score
+	pub loser_slash: Percent,
+	/// Distance from the aggregated score (multiplied by 1000) at which a juror outside the
+	/// incentives range gets the full `loser_slash`, the slash grows linearly with the distance
+	/// Zero for flat `loser_slash`
+	pub max_distance: i64,
+	/// Winners share `juror_incentives.1` and the stake slashed from losers pro rata to their stake
+	/// Otherwise winners share `juror_incentives.1` equally and the slashed stake goes to `Slash`
+	pub stake_weighted: bool,
+}
+
+/// Incentives shared by the jurors who voted for the decision
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct WinnersIncentives {
+	pub winners_count: u64,
+	pub winners_stake: u64,
+	pub slashed_stake: u64, // Stake slashed from losers, redistributed if `stake_weighted`
+}
+
+/// Progress of the settlement of a game, jurors are tallied before they are settled
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SettlementProgress {
+	pub tallied: u32, // Drawn jurors counted in `winners_incentives`
+	pub settled: u32, // Drawn jurors who got incentives or were slashed
+	pub winners_incentives: WinnersIncentives,
+}
+
+/// Settlement history of a juror over all schelling games
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct JurorStats {
+	/// Settled games the juror was drawn in
+	pub games_drawn: u32,
+	pub committed: u32,
+	pub revealed: u32,
+	/// Revealed votes that agreed with the decision of the game
+	pub coherent: u32,
+	pub slashed: u64,
+	/// Incentives earned, excluding the returned stake
+	pub rewards: u64,
 }
 
 
@@ -96,7 +164,89 @@ pub struct ScoreCommitVote {
 	pub revealed_vote: Option<i64>,
 }
 
+/// Commit of multi choice schelling game, `revealed_vote` is the index of the option
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MultiChoiceCommitVote {
+	pub commit: [u8; 32],
+	pub votestatus: VoteStatus,
+	pub revealed_vote: Option<u32>,
+}
 
+/// Reason of the penalty for a drawn juror who did not participate
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum NonParticipation {
+	NotCommitted,
+	NotRevealed,
+}
+
+/// Winning option of multi choice schelling game
+/// `Draw` when more than one option has the highest count of votes, or no vote is revealed
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum WinningOption {
+	Winner(u32),
+	Draw,
+}
+
+/// Choice of a juror as it is encoded in the commit hash
+/// The variant is selected by the schelling game type of the game
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum CommitChoice {
+	TwoChoice(u128),
+	Score(i64),
+	MultiChoice(u32),
+}
+
+/// Outcome of a decided schelling game
+/// The final score of score schelling game is in `GameResult::aggregated_score`
+/// `Undecided` if not enough jurors are drawn, `Cancelled` if the game is cancelled
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum GameOutcome {
+	TwoChoice(WinningDecision),
+	MultiChoice(WinningOption),
+	Score,
+	Undecided,
+	Cancelled,
+}
+
+/// Result of a schelling game, recorded when the game is decided
+/// It is kept after the game storage is pruned
+#[derive(PartialEq, Eq, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct GameResult<BlockNumber> {
+	pub outcome: GameOutcome,
+	/// Final score multiplied by 1000, only for score schelling game
+	pub aggregated_score: Option<i64>,
+	pub decided_at: BlockNumber,
+	/// Rounds played, number of appeals plus one
+	pub rounds: u32,
+	/// Drawn jurors of all rounds
+	pub juror_count: u32,
+	/// Evidence start time, or staking start time if there is no evidence period
+	pub started_at: BlockNumber,
+	/// Set once all drawn jurors are settled
+	pub settled_at: Option<BlockNumber>,
+}
+
+
+
+/// Strategy to aggregate the revealed scores of score schelling game into the final score
+/// 1) MeanWithinStdDev: mean of scores within one standard deviation of the mean
+/// 2) Median: median of scores, mean of the two middle scores for even count
+/// 3) InterquartileMean: mean of scores after dropping the lowest and highest quarter
+/// 4) StakeWeightedMean: mean of scores weighted by the stake of the juror
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ScoreAggregation {
+	MeanWithinStdDev,
+	Median,
+	InterquartileMean,
+	StakeWeightedMean,
+}
 
 /// RangePoint enum to determine whether score values are from
 /// 1) ZeroToTen: 0 to 10 
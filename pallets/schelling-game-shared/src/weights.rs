@@ -37,6 +37,12 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn do_something() -> Weight;
 	fn cause_error() -> Weight;
+	fn advance_periods(n: u32, ) -> Weight;
+	fn settle(n: u32, ) -> Weight;
+	fn prune_games(n: u32, e: u32, ) -> Weight;
+	fn set_phase_data() -> Weight;
+	fn cancel_game(n: u32, ) -> Weight;
+	fn refund_stakes(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_template using the Substrate node and recommended hardware.
@@ -63,6 +69,95 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	// The weights below are not benchmarked. They are conservative estimates: storage accesses
+	// are counted from the code and `ref_time` is a generous allowance for the computation.
+	// Replace them with benchmarked weights.
+
+	/// Storage: TemplateModule PeriodChangeQueue (r:1 w:1)
+	/// Storage: TemplateModule GamePhaseData (r:n w:n)
+	/// Storage: TemplateModule GameResults (r:2n w:0)
+	/// Storage: TemplateModule PeriodName (r:n w:n)
+	/// Storage: TemplateModule period start times and draw seed (r:2n w:3n)
+	/// Storage: TemplateModule PeriodChangeQueue of the next period (r:n w:n)
+	/// Cancellation for too few draws is charged separately with `cancel_game`
+	fn advance_periods(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule PeriodName (r:1 w:0)
+	/// Storage: TemplateModule GameSettlement (r:1 w:1)
+	/// Storage: TemplateModule GameResults (r:1 w:1)
+	/// Storage: TemplateModule DrawnJurors (r:1 w:0)
+	/// Storage: TemplateModule PastRoundDrawnJurors (r:2 w:0)
+	/// Storage: TemplateModule ReplacedJurors (r:1 w:0)
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: TemplateModule VoteCommits (r:n w:0)
+	/// Storage: TemplateModule JurorsIncentiveDistributedAccounts (r:n w:n)
+	/// Storage: TemplateModule JurorStatistics (r:n w:n)
+	/// Storage: System Account (r:n w:n)
+	fn settle(n: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: TemplateModule PruneQueue of a later block for games not fully pruned (r:n w:n)
+	/// Storage: TemplateModule per game storage (r:0 w:16n)
+	/// Storage: TemplateModule VoteCommits, ScoreVoteCommits, MultiChoiceVoteCommits,
+	/// UnstakedJurors, JurorsIncentiveDistributedAccounts and MultiChoiceCount prefixes (r:6n w:0)
+	/// Storage: `e` removed entries of the prefixes (r:e w:e)
+	fn prune_games(n: u32, e: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((17_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: TemplateModule GameTypePhaseData (r:0 w:1)
+	fn set_phase_data() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TemplateModule PeriodName (r:1 w:1)
+	/// Storage: TemplateModule GameResults (r:2 w:2)
+	/// Storage: TemplateModule DrawnJurors (r:1 w:1)
+	/// Storage: TemplateModule PastRoundDrawnJurors (r:3 w:3)
+	/// Storage: TemplateModule ReplacedJurors (r:1 w:1)
+	/// Storage: TemplateModule per game storage of the round (r:0 w:5)
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: SortitionSumGame SortitionSumTrees (r:1 w:1)
+	/// Storage: `n` refunded jurors, tree nodes and their parents (r:4n w:10n)
+	fn cancel_game(n: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule GameResults (r:1 w:0)
+	/// Storage: SortitionSumGame SortitionSumTrees (r:1 w:1)
+	/// Storage: SortitionSumGame tree removal (r:0 w:4)
+	/// Storage: `n` refunded jurors, tree nodes and their parents (r:4n w:10n)
+	fn refund_stakes(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
 }
 
 // For backwards compatibility and tests
@@ -88,4 +183,93 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	// The weights below are not benchmarked. They are conservative estimates: storage accesses
+	// are counted from the code and `ref_time` is a generous allowance for the computation.
+	// Replace them with benchmarked weights.
+
+	/// Storage: TemplateModule PeriodChangeQueue (r:1 w:1)
+	/// Storage: TemplateModule GamePhaseData (r:n w:n)
+	/// Storage: TemplateModule GameResults (r:2n w:0)
+	/// Storage: TemplateModule PeriodName (r:n w:n)
+	/// Storage: TemplateModule period start times and draw seed (r:2n w:3n)
+	/// Storage: TemplateModule PeriodChangeQueue of the next period (r:n w:n)
+	/// Cancellation for too few draws is charged separately with `cancel_game`
+	fn advance_periods(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule PeriodName (r:1 w:0)
+	/// Storage: TemplateModule GameSettlement (r:1 w:1)
+	/// Storage: TemplateModule GameResults (r:1 w:1)
+	/// Storage: TemplateModule DrawnJurors (r:1 w:0)
+	/// Storage: TemplateModule PastRoundDrawnJurors (r:2 w:0)
+	/// Storage: TemplateModule ReplacedJurors (r:1 w:0)
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: TemplateModule VoteCommits (r:n w:0)
+	/// Storage: TemplateModule JurorsIncentiveDistributedAccounts (r:n w:n)
+	/// Storage: TemplateModule JurorStatistics (r:n w:n)
+	/// Storage: System Account (r:n w:n)
+	fn settle(n: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: TemplateModule PruneQueue of a later block for games not fully pruned (r:n w:n)
+	/// Storage: TemplateModule per game storage (r:0 w:16n)
+	/// Storage: TemplateModule VoteCommits, ScoreVoteCommits, MultiChoiceVoteCommits,
+	/// UnstakedJurors, JurorsIncentiveDistributedAccounts and MultiChoiceCount prefixes (r:6n w:0)
+	/// Storage: `e` removed entries of the prefixes (r:e w:e)
+	fn prune_games(n: u32, e: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((17_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: TemplateModule GameTypePhaseData (r:0 w:1)
+	fn set_phase_data() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TemplateModule PeriodName (r:1 w:1)
+	/// Storage: TemplateModule GameResults (r:2 w:2)
+	/// Storage: TemplateModule DrawnJurors (r:1 w:1)
+	/// Storage: TemplateModule PastRoundDrawnJurors (r:3 w:3)
+	/// Storage: TemplateModule ReplacedJurors (r:1 w:1)
+	/// Storage: TemplateModule per game storage of the round (r:0 w:5)
+	/// Storage: TemplateModule PruneQueue (r:1 w:1)
+	/// Storage: SortitionSumGame SortitionSumTrees (r:1 w:1)
+	/// Storage: `n` refunded jurors, tree nodes and their parents (r:4n w:10n)
+	fn cancel_game(n: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TemplateModule GameResults (r:1 w:0)
+	/// Storage: SortitionSumGame SortitionSumTrees (r:1 w:1)
+	/// Storage: SortitionSumGame tree removal (r:0 w:4)
+	/// Storage: `n` refunded jurors, tree nodes and their parents (r:4n w:10n)
+	fn refund_stakes(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
 }
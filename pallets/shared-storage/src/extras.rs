@@ -15,6 +15,10 @@ impl<T: Config> SharedStorageLink for Pallet<T> {
 	fn set_positive_externality_link(address: Self::AccountId, score: i64)-> DispatchResult {
 		Self::set_positive_externality(address, score)
 	}
+
+	fn add_approved_citizen_address_link(address: Self::AccountId) -> DispatchResult {
+		Self::add_approved_citizen_address(address)
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -36,4 +40,16 @@ impl<T: Config> Pallet<T> {
 		PositiveExternalityScore::<T>::insert(address,score);
 		Ok(())
 	}
+
+	/// Add the citizen to the sorted `ApprovedCitizenAddress`, does nothing if already approved
+	pub(super) fn add_approved_citizen_address(address: T::AccountId) -> DispatchResult {
+		ApprovedCitizenAddress::<T>::try_mutate(|members| -> DispatchResult {
+			if let Err(index) = members.binary_search(&address) {
+				members
+					.try_insert(index, address)
+					.map_err(|_| Error::<T>::TooManyApprovedCitizens)?;
+			}
+			Ok(())
+		})
+	}
 }
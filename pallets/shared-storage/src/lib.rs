@@ -16,6 +16,7 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 mod extras;
+pub mod migrations;
 
 use frame_support::sp_std::{prelude::*};
 use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
@@ -29,8 +30,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 bounds the approved citizens
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -40,6 +44,9 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// Maximum number of approved citizens
+		#[pallet::constant]
+		type MaxApprovedCitizens: Get<u32>;
 	}
 
 	// The pallet's runtime storage items.
@@ -52,7 +59,8 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn approved_citizen_address)]
-	pub type ApprovedCitizenAddress<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>; // Its set, add element through binary_search
+	pub type ApprovedCitizenAddress<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxApprovedCitizens>, ValueQuery>; // Its set, add element through binary_search
 
 
 	#[pallet::storage]
@@ -74,7 +82,12 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			<ApprovedCitizenAddress<T>>::put(self.approved_citizen_address.clone());
+			let approved_citizen_address =
+				BoundedVec::<T::AccountId, T::MaxApprovedCitizens>::try_from(
+					self.approved_citizen_address.clone(),
+				)
+				.expect("More approved citizens than MaxApprovedCitizens");
+			<ApprovedCitizenAddress<T>>::put(approved_citizen_address);
 		}
 	}
 
@@ -96,6 +109,8 @@ pub mod pallet {
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
 		CitizenNotApproved,
+		/// Approved citizens reached `MaxApprovedCitizens`
+		TooManyApprovedCitizens,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
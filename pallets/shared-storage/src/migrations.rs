@@ -0,0 +1,39 @@
+//! Storage migrations of the shared storage pallet
+
+use crate::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+pub mod v1 {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub(crate) type ApprovedCitizenAddress<T: Config> =
+		StorageValue<Pallet<T>, Vec<AccountIdOf<T>>, ValueQuery>;
+
+	/// Bound the approved citizens
+	/// Citizens are sorted by account, citizens after the first `MaxApprovedCitizens` are no
+	/// longer approved
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let approved_citizen_address = ApprovedCitizenAddress::<T>::take();
+			let approved_citizen_address =
+				BoundedVec::<_, T::MaxApprovedCitizens>::truncate_from(approved_citizen_address);
+			<crate::ApprovedCitizenAddress<T>>::put(approved_citizen_address);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(2, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
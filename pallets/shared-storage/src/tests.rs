@@ -1,5 +1,8 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{migrations::v1, mock::*, Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 
 #[test]
 fn it_works_for_default_value() {
@@ -25,3 +28,41 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn add_approved_citizen_address_test() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::add_approved_citizen_address(3));
+		assert_ok!(TemplateModule::add_approved_citizen_address(1));
+		assert_ok!(TemplateModule::add_approved_citizen_address(3));
+		assert_ok!(TemplateModule::add_approved_citizen_address(2));
+		assert_eq!(TemplateModule::approved_citizen_address(), vec![1, 2, 3]);
+		assert_eq!(TemplateModule::get_approved_citizen_count(), 3);
+		assert_ok!(TemplateModule::check_citizen_is_approved(2));
+		assert_noop!(
+			TemplateModule::add_approved_citizen_address(4),
+			Error::<Test>::TooManyApprovedCitizens
+		);
+		assert_noop!(
+			TemplateModule::check_citizen_is_approved(4),
+			Error::<Test>::CitizenNotApproved
+		);
+	});
+}
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		v1::ApprovedCitizenAddress::<Test>::put(vec![1, 2, 3, 4, 5]);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert_eq!(TemplateModule::approved_citizen_address(), vec![1, 2, 3]);
+		assert_noop!(
+			TemplateModule::check_citizen_is_approved(4),
+			Error::<Test>::CitizenNotApproved
+		);
+	});
+}
@@ -0,0 +1,131 @@
+use codec::{Codec, Decode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+pub use sortition_sum_game_runtime_api::SortitionSumGameApi as SortitionSumGameRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// `key` is the SCALE encoded game key of the runtime
+#[rpc(client, server)]
+pub trait SortitionSumGameApi<BlockHash, AccountId> {
+	#[method(name = "sortitionsumgame_totalstake")]
+	fn total_stake(&self, key: Bytes, at: Option<BlockHash>) -> RpcResult<Option<u64>>;
+
+	#[method(name = "sortitionsumgame_stakeof")]
+	fn stake_of(&self, key: Bytes, who: AccountId, at: Option<BlockHash>)
+		-> RpcResult<Option<u64>>;
+
+	#[method(name = "sortitionsumgame_queryleafs")]
+	fn query_leafs(
+		&self,
+		key: Bytes,
+		cursor: u64,
+		count: u64,
+		at: Option<BlockHash>,
+	) -> RpcResult<(Vec<(AccountId, u64)>, bool)>;
+
+	#[method(name = "sortitionsumgame_stakerscount")]
+	fn stakers_count(&self, key: Bytes, at: Option<BlockHash>) -> RpcResult<u64>;
+}
+
+/// A struct that implements the `SortitionSumGameApi`.
+pub struct SortitionSumGame<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> SortitionSumGame<C, M> {
+	/// Create new `SortitionSumGame` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn decode_key<T: Decode>(bytes: &Bytes) -> RpcResult<T> {
+	T::decode(&mut &bytes[..]).map_err(|e| {
+		CallError::Custom(ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			"Unable to decode key",
+			Some(format!("{:?}", e)),
+		))
+		.into()
+	})
+}
+
+fn runtime_error<E: std::fmt::Debug>(e: E) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		9876, // No real reason for this value
+		"Something wrong",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+impl<C, Block, SumTreeName, AccountId> SortitionSumGameApiServer<<Block as BlockT>::Hash, AccountId>
+	for SortitionSumGame<C, (Block, SumTreeName)>
+where
+	Block: BlockT,
+	SumTreeName: Codec + Send + Sync + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: SortitionSumGameRuntimeApi<Block, SumTreeName, AccountId>,
+{
+	fn total_stake(
+		&self,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u64>> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let key: SumTreeName = decode_key(&key)?;
+		api.total_stake(at, key).map_err(runtime_error)
+	}
+
+	fn stake_of(
+		&self,
+		key: Bytes,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u64>> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let key: SumTreeName = decode_key(&key)?;
+		api.stake_of(at, key, who).map_err(runtime_error)
+	}
+
+	fn query_leafs(
+		&self,
+		key: Bytes,
+		cursor: u64,
+		count: u64,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(Vec<(AccountId, u64)>, bool)> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let key: SumTreeName = decode_key(&key)?;
+		api.query_leafs(at, key, cursor, count).map_err(runtime_error)
+	}
+
+	fn stakers_count(&self, key: Bytes, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u64> {
+		let api = self.client.runtime_api();
+		// If the block hash is not supplied assume the best block.
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let key: SumTreeName = decode_key(&key)?;
+		api.stakers_count(at, key).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_api::codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait SortitionSumGameApi<SumTreeName, AccountId>
+	where
+		SumTreeName: Codec,
+		AccountId: Codec,
+	{
+		/// Sum of the stakes in the tree, `None` if the tree does not exist
+		fn total_stake(key: SumTreeName) -> Option<u64>;
+		/// Stake of the account in the tree, `None` if the account has not staked
+		fn stake_of(key: SumTreeName, who: AccountId) -> Option<u64>;
+		/// Leaves from `cursor` with the account that owns them, and whether there are more
+		fn query_leafs(key: SumTreeName, cursor: u64, count: u64) -> (Vec<(AccountId, u64)>, bool);
+		/// Number of accounts with a stake in the tree
+		fn stakers_count(key: SumTreeName) -> u64;
+	}
+}
@@ -1,4 +1,6 @@
 use crate::*;
+use frame_support::sp_io;
+use frame_support::sp_std::collections::btree_map::BTreeMap;
 
 impl<T: Config> SortitionSumGameLink for Pallet<T> {
 	type SumTreeName = SumTreeNameType<T>;
@@ -19,9 +21,22 @@ impl<T: Config> SortitionSumGameLink for Pallet<T> {
     fn draw_link(key: Self::SumTreeName, draw_number: u64) -> Result<Self::AccountId, DispatchError> {
         Self::draw(key, draw_number)
     }
+	fn draw_many_link(
+		key: Self::SumTreeName,
+		count: u64,
+		seed: [u8; 32],
+	) -> Result<Vec<(Self::AccountId, u64)>, DispatchError> {
+		Self::draw_many(key, count, seed)
+	}
 	fn remove_tree_link(key: Self::SumTreeName) -> DispatchResult {
 		Self::remove_tree(key)
 	}
+	fn stakes_link(
+		key: Self::SumTreeName,
+		limit: u32,
+	) -> Result<Vec<(Self::AccountId, u64)>, DispatchError> {
+		Self::stakes(key, limit)
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -34,15 +49,9 @@ impl<T: Config> Pallet<T> {
 		match tree_option {
 			Some(_tree) => Err(Error::<T>::TreeAlreadyExists)?,
 			None => {
-				let mut sum_tree = SortitionSumTree {
-					k,
-					stack: Vec::new(),
-					nodes: Vec::new(),
-					ids_to_node_indexes: BTreeMap::new(),
-					node_indexes_to_ids: BTreeMap::new(),
-				};
+				let sum_tree = SortitionSumTree { k, nodes_len: 1, stack_len: 0 };
 
-				sum_tree.nodes.push(0);
+				<TreeNodes<T>>::insert(&key, 0, 0);
 
 				<SortitionSumTrees<T>>::insert(&key, &sum_tree);
 			},
@@ -55,49 +64,42 @@ impl<T: Config> Pallet<T> {
 
 		match tree_option {
 			None => Err(Error::<T>::TreeDoesnotExist)?,
-			Some(mut tree) => match tree.ids_to_node_indexes.get(&citizen_id) {
-				Some(tree_index_data) => {
-					let tree_index = *tree_index_data;
-					if tree_index == 0 {
-						Self::if_tree_index_zero(value, citizen_id, tree, tree_index, key);
-					} else {
-						// Existing node
-						if value == 0 {
-							let value = tree.nodes[tree_index as usize];
-							tree.nodes[tree_index as usize] = 0;
-							tree.stack.push(tree_index);
-							tree.ids_to_node_indexes.remove(&citizen_id);
-							tree.node_indexes_to_ids.remove(&tree_index);
-
-							// UpdateParents 🟥
-							Self::update_parents(tree, tree_index, false, value, key);
-						} else if value != tree.nodes[tree_index as usize] {
-							let plus_or_minus = tree.nodes[tree_index as usize] <= value;
-							let plus_or_minus_value = if plus_or_minus {
-								value
-									.checked_sub(tree.nodes[tree_index as usize])
-									.ok_or("StorageOverflow")?
-							} else {
-								(tree.nodes[tree_index as usize])
-									.checked_sub(value)
-									.ok_or("StorageOverflow")?
-							};
-							tree.nodes[tree_index as usize] = value;
-
-							// update parents 🟥
-							Self::update_parents(
-								tree,
-								tree_index,
-								plus_or_minus,
-								plus_or_minus_value,
-								key,
-							);
-						}
+			Some(tree) => match <IdsToNodeIndexes<T>>::get(&key, &citizen_id) {
+				Some(tree_index) if tree_index != 0 => {
+					let node_value = <TreeNodes<T>>::get(&key, tree_index);
+					// Existing node
+					if value == 0 {
+						<TreeNodes<T>>::insert(&key, tree_index, 0);
+						<TreeStack<T>>::insert(&key, tree.stack_len, tree_index);
+						<IdsToNodeIndexes<T>>::remove(&key, &citizen_id);
+						<NodeIndexesToIds<T>>::remove(&key, tree_index);
+						let tree = SortitionSumTree { stack_len: tree.stack_len + 1, ..tree };
+
+						// UpdateParents 🟥
+						Self::update_parents(&key, &tree, tree_index, false, node_value);
+						<SortitionSumTrees<T>>::insert(&key, tree);
+					} else if value != node_value {
+						let plus_or_minus = node_value <= value;
+						let plus_or_minus_value = if plus_or_minus {
+							value.checked_sub(node_value).ok_or("StorageOverflow")?
+						} else {
+							node_value.checked_sub(value).ok_or("StorageOverflow")?
+						};
+						<TreeNodes<T>>::insert(&key, tree_index, value);
+
+						// update parents 🟥
+						Self::update_parents(
+							&key,
+							&tree,
+							tree_index,
+							plus_or_minus,
+							plus_or_minus_value,
+						);
 					}
 				},
 
-				None => {
-					Self::if_tree_index_zero(value, citizen_id, tree, 0, key);
+				_ => {
+					Self::if_tree_index_zero(value, citizen_id, tree, key);
 				},
 			},
 		}
@@ -105,30 +107,31 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Add or subtract `value` from the parents of the node up to the root
 	fn update_parents(
-		mut tree: SortitionSumTree<AccountIdOf<T>>,
+		key: &SumTreeNameType<T>,
+		tree: &SortitionSumTree,
 		tree_index: u64,
 		plus_or_minus: bool,
 		value: u64,
-		key: SumTreeNameType<T>,
 	) {
 		let mut parent_index = tree_index;
 		while parent_index != 0 {
 			parent_index = (parent_index - 1) / tree.k;
-			tree.nodes[parent_index as usize] = if plus_or_minus {
-				(tree.nodes[parent_index as usize]).checked_add(value).expect("StorageOverflow")
-			} else {
-				(tree.nodes[parent_index as usize]).checked_sub(value).expect("StorageOverflow")
-			};
-
-			<SortitionSumTrees<T>>::insert(&key, &tree);
+			<TreeNodes<T>>::mutate(key, parent_index, |node| {
+				*node = if plus_or_minus {
+					node.checked_add(value).expect("StorageOverflow")
+				} else {
+					node.checked_sub(value).expect("StorageOverflow")
+				};
+			});
 		}
 	}
+
 	fn if_tree_index_zero(
 		value: u64,
 		citizen_id: AccountIdOf<T>,
-		mut tree: SortitionSumTree<AccountIdOf<T>>,
-		mut tree_index: u64,
+		mut tree: SortitionSumTree,
 		key: SumTreeNameType<T>,
 	) {
 		// No existing node.
@@ -136,37 +139,39 @@ impl<T: Config> Pallet<T> {
 			// Non zero value.
 			// Append.
 			// Add node.
-			if tree.stack.len() == 0 {
+			let tree_index;
+			if tree.stack_len == 0 {
 				// No vacant spots.
 				// Get the index and append the value.
-				tree_index = tree.nodes.len() as u64;
-				tree.nodes.push(value);
-
-				// println!("{}", tree_index);
+				tree_index = tree.nodes_len;
+				<TreeNodes<T>>::insert(&key, tree_index, value);
+				tree.nodes_len += 1;
 
 				// Potentially append a new node and make the parent a sum node.
 				if tree_index != 1 && (tree_index - 1) % tree.k == 0 {
 					// Is first child.
 					let parent_index = tree_index / tree.k;
-					let parent_id = tree.node_indexes_to_ids.get(&parent_index).unwrap().clone();
+					let parent_id = <NodeIndexesToIds<T>>::take(&key, parent_index).unwrap();
 					let new_index = tree_index + 1;
-					tree.nodes.push(*tree.nodes.get(parent_index as usize).unwrap());
-					tree.node_indexes_to_ids.remove(&parent_index);
-					tree.ids_to_node_indexes.insert(parent_id.clone(), new_index);
-					tree.node_indexes_to_ids.insert(new_index, parent_id);
+					let parent_value = <TreeNodes<T>>::get(&key, parent_index);
+					<TreeNodes<T>>::insert(&key, new_index, parent_value);
+					tree.nodes_len += 1;
+					<IdsToNodeIndexes<T>>::insert(&key, &parent_id, new_index);
+					<NodeIndexesToIds<T>>::insert(&key, new_index, parent_id);
 				}
 			} else {
-				let tree_index = tree.stack.get(tree.stack.len() - 1);
-				tree.nodes[*tree_index.unwrap() as usize] = value;
-				tree.stack.pop();
+				tree.stack_len -= 1;
+				tree_index = <TreeStack<T>>::take(&key, tree.stack_len).unwrap();
+				<TreeNodes<T>>::insert(&key, tree_index, value);
 			}
 
-			tree.ids_to_node_indexes.insert(citizen_id.clone(), tree_index);
-			tree.node_indexes_to_ids.insert(tree_index, citizen_id);
+			<IdsToNodeIndexes<T>>::insert(&key, &citizen_id, tree_index);
+			<NodeIndexesToIds<T>>::insert(&key, tree_index, citizen_id);
 
 			// update_parents 🟥
 
-			Self::update_parents(tree, tree_index, true, value, key);
+			Self::update_parents(&key, &tree, tree_index, true, value);
+			<SortitionSumTrees<T>>::insert(&key, tree);
 		}
 	}
 
@@ -174,25 +179,13 @@ impl<T: Config> Pallet<T> {
 		key: SumTreeNameType<T>,
 		citizen_id: AccountIdOf<T>,
 	) -> Result<Option<u64>, DispatchError> {
-		let tree_option = <SortitionSumTrees<T>>::get(&key);
-		match tree_option {
-			None => Err(Error::<T>::TreeDoesnotExist)?,
-			Some(tree) => {
-				let tree_index_data;
-				match tree.ids_to_node_indexes.get(&citizen_id) {
-					Some(v) => tree_index_data = v,
-					None => return Ok(None),
-				}
-
-				let value: u64;
-				let tree_index = *tree_index_data;
-				if tree_index == 0 {
-					value = 0;
-				} else {
-					value = tree.nodes[tree_index as usize];
-				}
+		ensure!(<SortitionSumTrees<T>>::contains_key(&key), Error::<T>::TreeDoesnotExist);
+		match <IdsToNodeIndexes<T>>::get(&key, &citizen_id) {
+			Some(tree_index) => {
+				let value = if tree_index == 0 { 0 } else { <TreeNodes<T>>::get(&key, tree_index) };
 				Ok(Some(value))
 			},
+			None => Ok(None),
 		}
 	}
 
@@ -202,26 +195,87 @@ impl<T: Config> Pallet<T> {
 		match tree_option {
 			None => Err(Error::<T>::TreeDoesnotExist)?,
 			Some(tree) => {
-				let mut tree_index = 0;
-				let mut current_draw_number = draw_number % tree.nodes[0];
+				let draw_number = draw_number % <TreeNodes<T>>::get(&key, 0);
+				let tree_index = Self::leaf_of_draw(&tree, draw_number, |node_index| {
+					<TreeNodes<T>>::get(&key, node_index)
+				});
+				let account_id = <NodeIndexesToIds<T>>::get(&key, tree_index).unwrap();
+				Ok(account_id)
+			},
+		}
+	}
 
-				while (tree.k * tree_index) + 1 < (tree.nodes.len() as u64) {
-					for i in 1..tree.k + 1 {
-						let node_index = (tree.k * tree_index) + i;
-						let node_value = tree.nodes[node_index as usize];
+	/// Draw up to `count` distinct accounts weighted by their stake, with their stake
+	/// Each drawn leaf is removed from an in memory copy of the sums, so the tree in storage is
+	/// unchanged. The draw number of the `i`th draw is derived from `blake2_256(SCALE(seed, i))`.
+	/// Fewer than `count` accounts are returned if the tree runs out of stake.
+	pub fn draw_many(
+		key: SumTreeNameType<T>,
+		count: u64,
+		seed: [u8; 32],
+	) -> Result<Vec<(AccountIdOf<T>, u64)>, DispatchError> {
+		let tree = <SortitionSumTrees<T>>::get(&key).ok_or(Error::<T>::TreeDoesnotExist)?;
+		// Node index, value of the nodes changed by the drawn leaves
+		let mut removed: BTreeMap<u64, u64> = BTreeMap::new();
+		let node_value = |removed: &BTreeMap<u64, u64>, node_index: u64| {
+			removed
+				.get(&node_index)
+				.copied()
+				.unwrap_or_else(|| <TreeNodes<T>>::get(&key, node_index))
+		};
 
-						if current_draw_number >= node_value {
-							current_draw_number -= node_value;
-						} else {
-							tree_index = node_index;
-							break;
-						}
-					}
+		let mut drawn = Vec::new();
+		for i in 0..count {
+			let total = node_value(&removed, 0);
+			if total == 0 {
+				break;
+			}
+			let random = sp_io::hashing::blake2_256(&(seed, i).encode());
+			let draw_number = u64::from_le_bytes(random[..8].try_into().expect("Slice of 8 bytes"));
+			let tree_index = Self::leaf_of_draw(&tree, draw_number % total, |node_index| {
+				node_value(&removed, node_index)
+			});
+			let value = node_value(&removed, tree_index);
+			let account_id =
+				<NodeIndexesToIds<T>>::get(&key, tree_index).ok_or(Error::<T>::TreeDoesnotExist)?;
+
+			// Remove the leaf until the end of the draw
+			removed.insert(tree_index, 0);
+			let mut parent_index = tree_index;
+			while parent_index != 0 {
+				parent_index = (parent_index - 1) / tree.k;
+				let parent_value = node_value(&removed, parent_index);
+				removed.insert(parent_index, parent_value.saturating_sub(value));
+			}
+			drawn.push((account_id, value));
+		}
+		Ok(drawn)
+	}
+
+	/// Walk from the root to the leaf that contains `draw_number`
+	/// `draw_number` must be less than the value of the root
+	fn leaf_of_draw(
+		tree: &SortitionSumTree,
+		draw_number: u64,
+		node_value: impl Fn(u64) -> u64,
+	) -> u64 {
+		let mut tree_index = 0;
+		let mut current_draw_number = draw_number;
+
+		while (tree.k * tree_index) + 1 < tree.nodes_len {
+			for i in 1..tree.k + 1 {
+				let node_index = (tree.k * tree_index) + i;
+				let value = node_value(node_index);
+
+				if current_draw_number >= value {
+					current_draw_number -= value;
+				} else {
+					tree_index = node_index;
+					break;
 				}
-				let account_id = tree.node_indexes_to_ids.get(&tree_index).unwrap().clone();
-				Ok(account_id)
-			},
+			}
 		}
+		tree_index
 	}
 
 	/**
@@ -244,26 +298,20 @@ impl<T: Config> Pallet<T> {
 			None => Err(Error::<T>::TreeDoesnotExist)?,
 			Some(tree) => {
 				let mut start_index = 0;
-				for i in 0..tree.nodes.len() {
-					if (tree.k * i as u64) + 1 >= tree.nodes.len() as u64 {
-						start_index = i as u64;
+				for i in 0..tree.nodes_len {
+					if (tree.k * i) + 1 >= tree.nodes_len {
+						start_index = i;
 						break;
 					}
 				}
 				let loop_start_index = start_index + cursor;
 
-				// let value = if loop_start_index + count > tree.nodes.len() as u64 {
-				// 	tree.nodes.len() as u64 - loop_start_index
-				// } else {
-				// 	count
-				// };
-
 				let mut values = Vec::new();
 				let mut values_index = 0;
 				let mut has_more = false;
-				for j in loop_start_index..tree.nodes.len() as u64 {
+				for j in loop_start_index..tree.nodes_len {
 					if values_index < count {
-						values.push(tree.nodes[j as usize]);
+						values.push(<TreeNodes<T>>::get(&key, j));
 						values_index = values_index + 1;
 					} else {
 						has_more = true;
@@ -276,8 +324,61 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	pub fn remove_tree(key: SumTreeNameType<T>)-> DispatchResult {
+	/// Leaves from `cursor` with the account that owns them, vacant leaves are skipped
+	/// Pagination is the same as `query_leafs`, the bool is whether there are more leaves
+	pub fn query_leafs_with_ids(
+		key: SumTreeNameType<T>,
+		cursor: u64,
+		count: u64,
+	) -> Result<(Vec<(AccountIdOf<T>, u64)>, bool), DispatchError> {
+		let (start_index, values, has_more) = Self::query_leafs(key.clone(), cursor, count)?;
+		let leafs = values
+			.into_iter()
+			.zip(start_index + cursor..)
+			.filter_map(|(value, tree_index)| {
+				<NodeIndexesToIds<T>>::get(&key, tree_index).map(|citizen_id| (citizen_id, value))
+			})
+			.collect();
+		Ok((leafs, has_more))
+	}
+
+	/// Sum of the stakes in the tree, `None` if the tree does not exist
+	pub fn total_stake(key: SumTreeNameType<T>) -> Option<u64> {
+		<SortitionSumTrees<T>>::contains_key(&key).then(|| <TreeNodes<T>>::get(&key, 0))
+	}
+
+	/// Number of accounts with a stake in the tree
+	pub fn stakers_count(key: SumTreeNameType<T>) -> Result<u64, DispatchError> {
+		ensure!(<SortitionSumTrees<T>>::contains_key(&key), Error::<T>::TreeDoesnotExist);
+		let count = <IdsToNodeIndexes<T>>::iter_prefix_values(&key)
+			.filter(|tree_index| *tree_index != 0)
+			.count();
+		Ok(count as u64)
+	}
+
+	/// At most `limit` accounts in the tree with their non zero stake
+	/// Accounts set to zero are removed from `IdsToNodeIndexes`, so setting the returned accounts
+	/// to zero gives the next page
+	pub fn stakes(
+		key: SumTreeNameType<T>,
+		limit: u32,
+	) -> Result<Vec<(AccountIdOf<T>, u64)>, DispatchError> {
+		ensure!(<SortitionSumTrees<T>>::contains_key(&key), Error::<T>::TreeDoesnotExist);
+		let stakes = <IdsToNodeIndexes<T>>::iter_prefix(&key)
+			.filter(|(_, tree_index)| *tree_index != 0)
+			.map(|(citizen_id, tree_index)| (citizen_id, <TreeNodes<T>>::get(&key, tree_index)))
+			.filter(|(_, value)| *value != 0)
+			.take(limit as usize)
+			.collect();
+		Ok(stakes)
+	}
+
+	pub fn remove_tree(key: SumTreeNameType<T>) -> DispatchResult {
 		<SortitionSumTrees<T>>::remove(&key);
+		let _ = <TreeNodes<T>>::clear_prefix(&key, u32::MAX, None);
+		let _ = <TreeStack<T>>::clear_prefix(&key, u32::MAX, None);
+		let _ = <IdsToNodeIndexes<T>>::clear_prefix(&key, u32::MAX, None);
+		let _ = <NodeIndexesToIds<T>>::clear_prefix(&key, u32::MAX, None);
 		Ok(())
 	}
 }
@@ -18,14 +18,15 @@ pub use weights::*;
 
 pub mod types;
 mod extras;
+pub mod migrations;
 
-use crate::types::{SortitionSumTree, SumTreeName};
-use frame_support::sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use crate::types::SortitionSumTree;
+use frame_support::sp_std::vec::Vec;
 use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
 use sortition_sum_game_link::SortitionSumGameLink;
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
-type SumTreeNameType<T> = SumTreeName<AccountIdOf<T>, BlockNumberOf<T>>;
+type SumTreeNameType<T> = <T as Config>::GameKey;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -33,8 +34,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// Storage version, 1 splits the sortition sum tree into per node storage
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -44,6 +48,8 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// Key of a sortition sum tree, one tree is created for each game
+		type GameKey: Parameter + Ord + MaxEncodedLen;
 	}
 
 	// The pallet's runtime storage items.
@@ -67,7 +73,51 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn sortition_sum_trees)]
 	pub type SortitionSumTrees<T> =
-		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, SortitionSumTree<AccountIdOf<T>>>;
+		StorageMap<_, Blake2_128Concat, SumTreeNameType<T>, SortitionSumTree>;
+
+	/// Nodes of the trees: (key, node index) => value
+	/// Leaves hold the stakes, other nodes the sum of their children
+	#[pallet::storage]
+	#[pallet::getter(fn tree_nodes)]
+	pub type TreeNodes<T> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Twox64Concat,
+		u64,
+		u64,
+		ValueQuery,
+	>;
+
+	/// Vacant leaves of the trees, reused by new stakes: (key, stack position) => node index
+	#[pallet::storage]
+	#[pallet::getter(fn tree_stack)]
+	pub type TreeStack<T> =
+		StorageDoubleMap<_, Blake2_128Concat, SumTreeNameType<T>, Twox64Concat, u64, u64>;
+
+	/// Leaf of the account in the tree: (key, account) => node index
+	#[pallet::storage]
+	#[pallet::getter(fn ids_to_node_indexes)]
+	pub type IdsToNodeIndexes<T> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		u64,
+	>;
+
+	/// Account of a leaf of the tree: (key, node index) => account
+	#[pallet::storage]
+	#[pallet::getter(fn node_indexes_to_ids)]
+	pub type NodeIndexesToIds<T> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		Twox64Concat,
+		u64,
+		AccountIdOf<T>,
+	>;
 
 	#[pallet::error]
 	pub enum Error<T> {
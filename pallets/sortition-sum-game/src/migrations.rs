@@ -0,0 +1,79 @@
+//! Storage migrations of the sortition sum game pallet
+
+use crate::*;
+use frame_support::sp_std::collections::btree_map::BTreeMap;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use scale_info::TypeInfo;
+
+pub mod v1 {
+	use super::*;
+
+	/// Sortition sum tree before version 1, the whole tree is a single storage value
+	#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct OldSortitionSumTree<AccountId> {
+		pub k: u64,
+		pub stack: Vec<u64>,
+		pub nodes: Vec<u64>,
+		pub ids_to_node_indexes: BTreeMap<AccountId, u64>, // citizen id, node index
+		pub node_indexes_to_ids: BTreeMap<u64, AccountId>, // node index, citizen id
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type SortitionSumTrees<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		SumTreeNameType<T>,
+		OldSortitionSumTree<AccountIdOf<T>>,
+	>;
+
+	/// Move the nodes, vacant leaves and accounts of every tree into separate storage
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let old_trees: Vec<_> = SortitionSumTrees::<T>::drain().collect();
+			let mut writes = 1u64;
+			for (key, old_tree) in old_trees.iter() {
+				for (tree_index, value) in old_tree.nodes.iter().enumerate() {
+					<TreeNodes<T>>::insert(key, tree_index as u64, value);
+				}
+				for (position, tree_index) in old_tree.stack.iter().enumerate() {
+					<TreeStack<T>>::insert(key, position as u64, tree_index);
+				}
+				for (citizen_id, tree_index) in old_tree.ids_to_node_indexes.iter() {
+					<IdsToNodeIndexes<T>>::insert(key, citizen_id, tree_index);
+				}
+				for (tree_index, citizen_id) in old_tree.node_indexes_to_ids.iter() {
+					<NodeIndexesToIds<T>>::insert(key, tree_index, citizen_id);
+				}
+				let tree = SortitionSumTree {
+					k: old_tree.k,
+					nodes_len: old_tree.nodes.len() as u64,
+					stack_len: old_tree.stack.len() as u64,
+				};
+				<crate::SortitionSumTrees<T>>::insert(key, tree);
+				// Old tree removed and new tree inserted
+				writes += 2
+					+ old_tree.nodes.len() as u64
+					+ old_tree.stack.len() as u64
+					+ old_tree.ids_to_node_indexes.len() as u64
+					+ old_tree.node_indexes_to_ids.len() as u64;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			let reads = 1 + old_trees.len() as u64;
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version is not 1");
+			Ok(())
+		}
+	}
+}
@@ -1,4 +1,5 @@
 use crate as pallet_template;
+use crate::types::SumTreeName;
 use frame_support::traits::{ConstU16, ConstU64};
 use sp_core::H256;
 use sp_runtime::{
@@ -51,6 +52,7 @@ impl frame_system::Config for Test {
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type GameKey = SumTreeName<u64, u64>;
 }
 
 // Build genesis storage according to the mock runtime.
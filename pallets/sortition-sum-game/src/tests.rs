@@ -1,5 +1,14 @@
-use crate::{mock::*, types::SumTreeName, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations::v1,
+	mock::*,
+	types::{SortitionSumTree, SumTreeName},
+	Error, Event,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	sp_std::collections::btree_map::BTreeMap,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 
 #[test]
 fn it_works_for_default_value() {
@@ -40,3 +49,123 @@ fn correct_error_for_none_value() {
 		println!("{:?}", data);
 	});
 }
+
+#[test]
+fn vacant_leaf_is_reused_test() {
+	new_test_ext().execute_with(|| {
+		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 10 };
+		assert_ok!(TemplateModule::create_tree(key.clone(), 2));
+		assert_ok!(TemplateModule::set(key.clone(), 10, 1));
+		assert_ok!(TemplateModule::set(key.clone(), 20, 2));
+		assert_ok!(TemplateModule::set(key.clone(), 30, 3));
+		assert_ok!(TemplateModule::set(key.clone(), 0, 2));
+		assert_eq!(TemplateModule::tree_nodes(key.clone(), 0), 40);
+		assert_eq!(TemplateModule::sortition_sum_trees(key.clone()).unwrap().stack_len, 1);
+
+		assert_ok!(TemplateModule::set(key.clone(), 50, 4));
+		assert_eq!(TemplateModule::sortition_sum_trees(key.clone()).unwrap().stack_len, 0);
+		assert_eq!(TemplateModule::stake_of(key.clone(), 4), Ok(Some(50)));
+		assert_eq!(TemplateModule::stake_of(key.clone(), 2), Ok(None));
+		assert_eq!(TemplateModule::tree_nodes(key.clone(), 0), 90);
+		let mut stakes = TemplateModule::stakes(key.clone(), 10).unwrap();
+		stakes.sort();
+		assert_eq!(stakes, vec![(1, 10), (3, 30), (4, 50)]);
+		assert_eq!(TemplateModule::stakes(key.clone(), 2).unwrap().len(), 2);
+
+		assert_ok!(TemplateModule::remove_tree(key.clone()));
+		assert_eq!(TemplateModule::sortition_sum_trees(key.clone()), None);
+		assert_eq!(TemplateModule::tree_nodes(key.clone(), 0), 0);
+		assert_eq!(TemplateModule::ids_to_node_indexes(key.clone(), 1), None);
+	});
+}
+
+#[test]
+fn draw_many_test() {
+	new_test_ext().execute_with(|| {
+		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 10 };
+		assert_noop!(
+			TemplateModule::draw_many(key.clone(), 2, [0; 32]),
+			Error::<Test>::TreeDoesnotExist
+		);
+		assert_ok!(TemplateModule::create_tree(key.clone(), 2));
+		assert_ok!(TemplateModule::set(key.clone(), 10, 1));
+		assert_ok!(TemplateModule::set(key.clone(), 20, 2));
+		assert_ok!(TemplateModule::set(key.clone(), 30, 3));
+		assert_ok!(TemplateModule::set(key.clone(), 40, 4));
+
+		let drawn = TemplateModule::draw_many(key.clone(), 3, [7; 32]).unwrap();
+		assert_eq!(drawn.len(), 3);
+		for (citizen_id, stake) in drawn.iter() {
+			assert_eq!(*stake, *citizen_id * 10);
+			assert_eq!(drawn.iter().filter(|(id, _)| id == citizen_id).count(), 1);
+		}
+		// Same seed, same draw
+		assert_eq!(TemplateModule::draw_many(key.clone(), 3, [7; 32]), Ok(drawn));
+
+		// Stops once every account is drawn
+		let mut drawn = TemplateModule::draw_many(key.clone(), 10, [8; 32]).unwrap();
+		drawn.sort();
+		assert_eq!(drawn, vec![(1, 10), (2, 20), (3, 30), (4, 40)]);
+
+		// The tree is unchanged
+		assert_eq!(TemplateModule::tree_nodes(&key, 0), 100);
+		assert_eq!(TemplateModule::stake_of(key.clone(), 4), Ok(Some(40)));
+	});
+}
+
+#[test]
+fn query_tree_test() {
+	new_test_ext().execute_with(|| {
+		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 10 };
+		assert_eq!(TemplateModule::total_stake(key.clone()), None);
+		assert_noop!(TemplateModule::stakers_count(key.clone()), Error::<Test>::TreeDoesnotExist);
+		assert_ok!(TemplateModule::create_tree(key.clone(), 2));
+		assert_eq!(TemplateModule::total_stake(key.clone()), Some(0));
+		assert_ok!(TemplateModule::set(key.clone(), 10, 1));
+		assert_ok!(TemplateModule::set(key.clone(), 20, 2));
+		assert_ok!(TemplateModule::set(key.clone(), 30, 3));
+		assert_ok!(TemplateModule::set(key.clone(), 0, 2));
+
+		assert_eq!(TemplateModule::total_stake(key.clone()), Some(40));
+		assert_eq!(TemplateModule::stakers_count(key.clone()), Ok(2));
+		// Leaves are 2 (vacant), 3 and 4
+		assert_eq!(
+			TemplateModule::query_leafs_with_ids(key.clone(), 0, 3),
+			Ok((vec![(3, 30), (1, 10)], false))
+		);
+		assert_eq!(
+			TemplateModule::query_leafs_with_ids(key.clone(), 0, 2),
+			Ok((vec![(3, 30)], true))
+		);
+		assert_eq!(
+			TemplateModule::query_leafs_with_ids(key.clone(), 2, 2),
+			Ok((vec![(1, 10)], false))
+		);
+	});
+}
+
+#[test]
+fn migration_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		let key = SumTreeName::ProfileValidation { citizen_address: 1, block_number: 10 };
+		let old_tree = v1::OldSortitionSumTree {
+			k: 2,
+			stack: vec![],
+			nodes: vec![30, 10, 20],
+			ids_to_node_indexes: BTreeMap::from([(1, 1), (2, 2)]),
+			node_indexes_to_ids: BTreeMap::from([(1, 1), (2, 2)]),
+		};
+		v1::SortitionSumTrees::<Test>::insert(&key, old_tree);
+
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		let tree = TemplateModule::sortition_sum_trees(key.clone()).unwrap();
+		assert_eq!(tree, SortitionSumTree { k: 2, nodes_len: 3, stack_len: 0 });
+		assert_eq!(TemplateModule::stake_of(key.clone(), 1), Ok(Some(10)));
+		assert_eq!(TemplateModule::stake_of(key.clone(), 2), Ok(Some(20)));
+		assert_eq!(TemplateModule::draw(key.clone(), 15), Ok(2));
+		assert_ok!(TemplateModule::set(key.clone(), 40, 3));
+		assert_eq!(TemplateModule::tree_nodes(key.clone(), 0), 70);
+	});
+}
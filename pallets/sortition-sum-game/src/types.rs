@@ -1,10 +1,11 @@
 use frame_support::{pallet_prelude::*};
-use frame_support::sp_std::{vec::Vec, collections::btree_map::BTreeMap};
 use scale_info::TypeInfo;
 
 type CitizenId = u64;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo)]
+/// Game keys used by the tests of the sortition sum game and schelling game shared pallets
+/// Runtimes define their own `GameKey` from the keys of the pallets that create games
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub enum SumTreeName<AccountId, BlockNumber> {
     ProfileValidation { citizen_address: AccountId, block_number: BlockNumber},
@@ -13,14 +14,15 @@ pub enum SumTreeName<AccountId, BlockNumber> {
 }
 
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo)]
+/// Parameters of a sortition sum tree
+/// Nodes, vacant leaves and the accounts of the leaves are kept in separate storage,
+/// so that an operation only reads and writes the nodes on the path to the root
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Debug))]
-pub struct SortitionSumTree<AccountId> {
+pub struct SortitionSumTree {
     pub k: u64,
-    pub stack: Vec<u64>,
-    pub nodes: Vec<u64>,
-    pub ids_to_node_indexes: BTreeMap<AccountId, u64>, // citizen id, node index
-    pub node_indexes_to_ids: BTreeMap<u64, AccountId>, // node index, citizen id
+    pub nodes_len: u64, // Number of nodes ever appended
+    pub stack_len: u64, // Number of vacant leaves
 }
 
 
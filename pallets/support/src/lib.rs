@@ -12,7 +12,7 @@ pub type SpaceId = u64;
 pub type PostId = u64;
 pub type PositiveExternalityPostId = u64;
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct WhoAndWhen<AccountId, BlockNumber, Moment> {
     pub account: AccountId,
     pub block: BlockNumber,
@@ -38,22 +38,63 @@ where
     }
 }
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+/// Maximum length of content.
+/// Valid content is an IPFS CID of at most 59 bytes, the bound is larger so that content stored
+/// before it was bounded is kept by the storage migrations.
+pub const MAX_CONTENT_LENGTH: u32 = 1024;
+
+/// Bytes of content, any content that passes `ensure_content_is_valid` fits.
+pub type ContentBytes = BoundedVec<u8, ConstU32<MAX_CONTENT_LENGTH>>;
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Content {
     /// No content.
     None,
     /// A raw vector of bytes.
-    Other(Vec<u8>),
+    Other(ContentBytes),
     /// IPFS CID v0 of content.
-    IPFS(Vec<u8>),
+    IPFS(ContentBytes),
 }
 
 impl From<Content> for Vec<u8> {
     fn from(content: Content) -> Vec<u8> {
         match content {
             Content::None => vec![],
-            Content::Other(vec_u8) => vec_u8,
-            Content::IPFS(vec_u8) => vec_u8,
+            Content::Other(vec_u8) => vec_u8.into_inner(),
+            Content::IPFS(vec_u8) => vec_u8.into_inner(),
+        }
+    }
+}
+
+/// Content before it was bounded, read by the storage migrations of the pallets that store content.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum OldContent {
+    None,
+    Other(Vec<u8>),
+    IPFS(Vec<u8>),
+}
+
+impl OldContent {
+    /// Bounded content of the storage entry `entry`.
+    /// Content longer than `MAX_CONTENT_LENGTH` is truncated rather than removed, and logged with
+    /// its entry and length.
+    pub fn into_bounded<E: core::fmt::Debug>(self, entry: E) -> Content {
+        let bound = |bytes: Vec<u8>| {
+            if bytes.len() > MAX_CONTENT_LENGTH as usize {
+                log::warn!(
+                    target: "runtime::support",
+                    "Content of {:?} is truncated from {} to {} bytes",
+                    entry,
+                    bytes.len(),
+                    MAX_CONTENT_LENGTH,
+                );
+            }
+            ContentBytes::truncate_from(bytes)
+        };
+        match self {
+            OldContent::None => Content::None,
+            OldContent::Other(bytes) => Content::Other(bound(bytes)),
+            OldContent::IPFS(bytes) => Content::IPFS(bound(bytes)),
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64, GenesisBuild};
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, GenesisBuild};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -54,6 +54,7 @@ impl frame_system::Config for Test {
 impl shared_storage::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type MaxApprovedCitizens = ConstU32<100>;
 }
 
 impl pallet_template::Config for Test {
@@ -16,7 +16,7 @@ use sp_runtime::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -40,7 +40,14 @@ pub use frame_support::{
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::dispatch::DispatchResult;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use profile_validation::ProfileValidationKey;
+use scale_info::TypeInfo;
+use schelling_game_shared::randomness::FutureBlockRandomness;
+use schelling_game_shared::types::{CommitChoice, GameResult, JurorStats};
+use schelling_game_shared_link::OnGameConcluded;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
@@ -67,6 +74,22 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// Key of a schelling game and of its sortition sum tree.
+/// Each pallet that creates games has a variant holding the key of its games.
+#[derive(
+	Clone, Encode, Decode, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum GameKey {
+	#[codec(index = 0)]
+	ProfileValidation(ProfileValidationKey<AccountId, BlockNumber>),
+}
+
+impl From<ProfileValidationKey<AccountId, BlockNumber>> for GameKey {
+	fn from(key: ProfileValidationKey<AccountId, BlockNumber>) -> Self {
+		GameKey::ProfileValidation(key)
+	}
+}
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -279,15 +302,32 @@ impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
 impl sortition_sum_game::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = sortition_sum_game::weights::SubstrateWeight<Runtime>;
+	type GameKey = GameKey;
 }
 
 impl schelling_game_shared::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = schelling_game_shared::weights::SubstrateWeight<Runtime>;	
 	type Currency = Balances;
-	type RandomnessSource = RandomnessCollectiveFlip;
+	// Jurors are drawn with randomness determined after the drawing period starts
+	// Collective flip randomness is known since 81 blocks before the current block, so jurors
+	// can be drawn 82 blocks after the drawing period starts
+	type RandomnessSource =
+		FutureBlockRandomness<RandomnessCollectiveFlip, ConstU32<1>, ConstU32<81>>;
 	type Slash = ();
 	type Reward = ();
+	type MaxPeriodChangesPerBlock = ConstU32<50>;
+	// Settled games are pruned a week after settlement, `GameResults` are kept
+	type GameRetentionPeriod = ConstU32<{ 7 * DAYS }>;
+	type MaxGamesPrunedPerBlock = ConstU32<50>;
+	// 30 jurors in the first round are 123 jurors after two appeals
+	type MaxDrawsPerRound = ConstU32<128>;
+	// Salts of 16 random bytes can not be guessed from the choice of the juror
+	type MinSaltLength = ConstU32<16>;
+	type PhaseDataOrigin = frame_system::EnsureRoot<AccountId>;
+	// Results are routed to the pallet that created the game
+	type OnGameConcluded = GameConcludedRouter;
+	type GameKey = GameKey;
 	type SortitionSumGameSource = SortitionSumGame;
 }
 
@@ -295,7 +335,27 @@ impl profile_validation::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = profile_validation::weights::SubstrateWeight<Runtime>;	
 	type Currency = Balances;
+	type SchellingGameKey = GameKey;
 	type SchellingGameSharedSource = SchellingGameShared;
+	type SharedStorageSource = SharedStorage;
+	type MaxChallengePostComments = ConstU32<1_000>;
+}
+
+impl shared_storage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = shared_storage::weights::SubstrateWeight<Runtime>;
+	type MaxApprovedCitizens = ConstU32<10_000>;
+}
+
+/// Routes the result of a schelling game to the pallet that created the game, by `GameKey` variant
+pub struct GameConcludedRouter;
+
+impl OnGameConcluded<GameKey, GameResult<BlockNumber>> for GameConcludedRouter {
+	fn on_game_concluded(key: GameKey, result: GameResult<BlockNumber>) -> DispatchResult {
+		match key {
+			GameKey::ProfileValidation(key) => ProfileValidation::on_game_concluded(key, result),
+		}
+	}
 }
 
 
@@ -322,7 +382,7 @@ construct_runtime!(
 		SortitionSumGame: sortition_sum_game,
 		SchellingGameShared: schelling_game_shared,
 		ProfileValidation: profile_validation,
-
+		SharedStorage: shared_storage,
 	}
 );
 
@@ -349,6 +409,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to run on runtime upgrade.
+pub type Migrations = (
+	sortition_sum_game::migrations::v1::MigrateToV1<Runtime>,
+	schelling_game_shared::migrations::v1::MigrateToV1<Runtime>,
+	shared_storage::migrations::v1::MigrateToV1<Runtime>,
+	profile_validation::migrations::v1::MigrateToV1<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -356,6 +423,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -541,6 +609,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sortition_sum_game_runtime_api::SortitionSumGameApi<Block, GameKey, AccountId> for Runtime {
+		fn total_stake(key: GameKey) -> Option<u64> {
+			SortitionSumGame::total_stake(key)
+		}
+		fn stake_of(key: GameKey, who: AccountId) -> Option<u64> {
+			SortitionSumGame::stake_of(key, who).ok().flatten()
+		}
+		fn query_leafs(key: GameKey, cursor: u64, count: u64) -> (Vec<(AccountId, u64)>, bool) {
+			SortitionSumGame::query_leafs_with_ids(key, cursor, count).unwrap_or_default()
+		}
+		fn stakers_count(key: GameKey) -> u64 {
+			SortitionSumGame::stakers_count(key).unwrap_or_default()
+		}
+	}
+
+	impl schelling_game_shared_runtime_api::SchellingGameSharedApi<Block, GameKey, AccountId, CommitChoice, JurorStats>
+		for Runtime
+	{
+		fn commit_hash(key: GameKey, who: AccountId, choice: CommitChoice, salt: Vec<u8>) -> [u8; 32] {
+			SchellingGameShared::commit_hash(&key, &who, &choice, &salt)
+		}
+		fn min_salt_length() -> u32 {
+			SchellingGameShared::min_salt_length()
+		}
+		fn juror_stats(who: AccountId) -> JurorStats {
+			SchellingGameShared::juror_stats(who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1,5 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::sp_std::prelude::*;
 
 pub trait SchellingGameSharedLink {
@@ -9,20 +9,27 @@ pub trait SchellingGameSharedLink {
 	type AccountId;
 	type Balance;
 	type RangePoint;
+	type ScoreAggregation;
 	type Period;
 	type PhaseData;
+	type PhaseLengths;
+	type GameResult;
 
 	fn create_phase_data(
-		block_length: u64,
+		phase_lengths: Self::PhaseLengths,
 		max_draws: u64,
 		min_number_juror_staked: u64,
 		min_juror_stake: u64,
 		juror_incentives: (u64, u64),
-	) -> Self::PhaseData;
+		max_appeals: u32,
+	) -> Result<Self::PhaseData, DispatchError>;
+	fn get_phase_data_link(game_type: Self::SchellingGameType) -> Option<Self::PhaseData>;
+	fn get_game_phase_data_link(key: Self::SumTreeName) -> Option<Self::PhaseData>;
 	fn get_period_link(key: Self::SumTreeName) -> Option<Self::Period>;
 
 	fn set_to_evidence_period_link(
 		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
 		now: Self::BlockNumber,
 	) -> DispatchResult;
 	fn create_tree_helper_link(key: Self::SumTreeName, k: u64) -> DispatchResult;
@@ -35,6 +42,7 @@ pub trait SchellingGameSharedLink {
 
 	fn set_to_staking_period_pe_link(
 		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
 		now: Self::BlockNumber,
 	) -> DispatchResult;
 	fn change_period_link(
@@ -65,11 +73,6 @@ pub trait SchellingGameSharedLink {
 		choice: u128,
 		salt: Vec<u8>,
 	) -> DispatchResult;
-	fn get_incentives_two_choice_helper_link(
-		key: Self::SumTreeName,
-		phase_data: Self::PhaseData,
-		who: Self::AccountId,
-	) -> DispatchResult;
 	fn get_evidence_period_end_block_helper_link(
 		key: Self::SumTreeName,
 		phase_data: Self::PhaseData,
@@ -94,6 +97,18 @@ pub trait SchellingGameSharedLink {
 		phase_data: Self::PhaseData,
 		now: Self::BlockNumber,
 	) -> Option<u32>;
+	fn get_appeal_period_end_block_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		now: Self::BlockNumber,
+	) -> Option<u32>;
+	fn appeal_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		who: Self::AccountId,
+		appellant_choice: Option<u32>,
+		now: Self::BlockNumber,
+	) -> DispatchResult;
 	fn selected_as_juror_helper_link(key: Self::SumTreeName, who: Self::AccountId) -> bool;
 	fn commit_vote_for_score_helper_link(
 		key: Self::SumTreeName,
@@ -107,18 +122,61 @@ pub trait SchellingGameSharedLink {
 		salt: Vec<u8>,
 	) -> DispatchResult;
 
-	fn get_incentives_score_schelling_helper_link(
+	fn get_mean_value_link(key: Self::SumTreeName) -> i64;
+
+	fn get_drawn_jurors(key: Self::SumTreeName) -> Vec<(Self::AccountId, u64)>;
+
+	fn set_number_of_options_link(key: Self::SumTreeName, number_of_options: u32)
+		-> DispatchResult;
+
+	fn commit_vote_multi_choice_helper_link(
 		key: Self::SumTreeName,
-		phase_data: Self::PhaseData,
-		range_point: Self::RangePoint,
+		who: Self::AccountId,
+		vote_commit: [u8; 32],
 	) -> DispatchResult;
 
-	fn get_mean_value_link(key: Self::SumTreeName) -> i64;
+	fn reveal_vote_multi_choice_helper_link(
+		key: Self::SumTreeName,
+		who: Self::AccountId,
+		choice: u32,
+		salt: Vec<u8>,
+	) -> DispatchResult;
+
+	fn get_winning_option_link(key: Self::SumTreeName) -> Option<u32>;
 
-	fn get_all_incentives_two_choice_helper(
+	fn settle_two_choice_helper_link(
 		key: Self::SumTreeName,
 		phase_data: Self::PhaseData,
-	) -> DispatchResult;
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError>;
 
-	fn get_drawn_jurors(key: Self::SumTreeName) -> Vec<(Self::AccountId, u64)>;
+	fn settle_score_schelling_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		range_point: Self::RangePoint,
+		score_aggregation: Self::ScoreAggregation,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError>;
+
+	fn settle_multi_choice_helper_link(
+		key: Self::SumTreeName,
+		phase_data: Self::PhaseData,
+		limit: u32,
+	) -> Result<(u32, bool), DispatchError>;
+
+	fn get_result_link(key: Self::SumTreeName) -> Option<Self::GameResult>;
+
+	fn cancel_game_link(key: Self::SumTreeName) -> DispatchResult;
+}
+
+/// Called back when the outcome of a schelling game is decided
+/// Implemented by the pallets that create schelling games, `()` does nothing
+pub trait OnGameConcluded<SumTreeName, GameResult> {
+	fn on_game_concluded(key: SumTreeName, result: GameResult) -> DispatchResult;
+}
+
+impl<SumTreeName, GameResult> OnGameConcluded<SumTreeName, GameResult> for () {
+	fn on_game_concluded(_key: SumTreeName, _result: GameResult) -> DispatchResult {
+		Ok(())
+	}
 }
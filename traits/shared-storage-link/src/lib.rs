@@ -9,5 +9,6 @@ pub trait SharedStorageLink {
 
 	fn get_approved_citizen_count_link() -> u64;
 	fn set_positive_externality_link(address: Self::AccountId, score: i64)-> DispatchResult;
+	fn add_approved_citizen_address_link(address: Self::AccountId) -> DispatchResult;
 	
 }
@@ -1,4 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+use frame_support::sp_std::prelude::*;
 use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
 
 pub trait SortitionSumGameLink {
@@ -11,5 +12,14 @@ pub trait SortitionSumGameLink {
 		citizen_id: Self::AccountId,
 	) -> Result<Option<u64>, DispatchError>;
 	fn draw_link(key: Self::SumTreeName, draw_number: u64) -> Result<Self::AccountId, DispatchError>;
+	fn draw_many_link(
+		key: Self::SumTreeName,
+		count: u64,
+		seed: [u8; 32],
+	) -> Result<Vec<(Self::AccountId, u64)>, DispatchError>;
 	fn remove_tree_link(key: Self::SumTreeName) -> DispatchResult;
+	fn stakes_link(
+		key: Self::SumTreeName,
+		limit: u32,
+	) -> Result<Vec<(Self::AccountId, u64)>, DispatchError>;
 }